@@ -1,43 +1,26 @@
 //! Main insurance contract implementation
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Bytes, Map, Symbol, Vec, panic_with_error};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Bytes, Map, String, Symbol, Vec, panic_with_error};
 
-use crate::shared::{AccessControl, ContractError, PolicyStatus, ClaimStatus, ReentrancyGuard};
+use crate::shared::{generate_id, next_id_nonce, is_zero_address, require_nonzero_address, CONTRACT_VERSION, to_base_units, to_whole_units, AccessControl, ContractError, PolicyStatus, ClaimStatus, InitGuard, SystemGuard};
 use crate::insurance::{
-    types::{Policy, Claim, ClaimEvidence, CreatePolicyParams, PolicyStats},
+    types::{Policy, Claim, BalanceReportClient, BatchPayoutSummary, ClaimEvidence, ContractInfo, ConversionRateClient, CreatePolicyParams, EmergencyFundClient, FundingBreakdown, FundSource, PolicyReceipt, PolicyStats, PriceOracleClient, RejectionReason, RejectionStats, SystemHealth, RATE_SCALE},
 };
 
+/// Number of most recent config snapshots kept for rollback
+pub const MAX_CONFIG_SNAPSHOTS: u32 = 10;
+
 /// Insurance contract for policy management, premium collection, and claim processing
-#[contracttype]
-pub struct InsuranceContract {
-    /// Mapping from policy ID to Policy data
-    policies: Map<Bytes, Policy>,
-    /// Mapping from policy holder to their policies
-    user_policies: Map<Address, Vec<Bytes>>,
-    /// Mapping from claim ID to Claim data
-    claims: Map<Bytes, Claim>,
-    /// Global policy statistics
-    stats: PolicyStats,
-    /// Risk pool balance (for claims)
-    risk_pool_balance: i128,
-    /// Premium pool balance (collected premiums)
-    premium_pool_balance: i128,
-    /// Authorized administrators
-    authorized_admins: Vec<Address>,
-    /// Authorized claim processors
-    authorized_processors: Vec<Address>,
-    /// Reentrancy guard
-    reentrancy_guard: ReentrancyGuard,
-    /// Configuration
-    config: InsuranceConfig,
-}
+#[contract]
+pub struct InsuranceContract;
 
 /// Insurance contract configuration
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct InsuranceConfig {
-    /// Minimum coverage amount
+    /// Minimum coverage amount, in whole tokens
     pub min_coverage: i128,
-    /// Maximum coverage amount
+    /// Maximum coverage amount, in whole tokens
     pub max_coverage: i128,
     /// Minimum policy duration in seconds (1 day)
     pub min_duration: u64,
@@ -45,20 +28,92 @@ pub struct InsuranceConfig {
     pub max_duration: u64,
     /// Required approvals for claim processing
     pub claim_processing_approvals: u32,
+    /// Required approvals for claims from addresses with prior fraud flags
+    pub flagged_claim_approvals: u32,
     /// Maximum risk score (0-100)
     pub max_risk_score: u32,
+    /// Time after expiry during which a policy can still be renewed, in seconds
+    pub renewal_grace_period: u64,
+    /// Minimum time between claims on the same policy, in seconds
+    pub claim_cooldown: u64,
+    /// Delay between claim approval and payout, in seconds
+    pub settlement_delay: u64,
+    /// Pool utilization (coverage / risk pool) above which quotes are surcharged, in bps
+    pub utilization_threshold_bps: u32,
+    /// Surcharge added to quotes above the utilization threshold, in bps (0 disables)
+    pub utilization_surcharge_bps: u32,
+    /// Minimum premium as a share of coverage, in bps (0 disables)
+    pub min_premium_bps: u32,
+    /// Maximum number of pending claims allowed on a single policy
+    pub max_pending_claims_per_policy: u32,
+    /// Maximum number of evidence items attached to a single claim
+    pub max_evidence_items: u32,
+    /// Whether total-loss payouts refund the unused premium to the holder
+    pub refund_unused_premium: bool,
+    /// Allowed deviation between claimed and oracle-reported market value, in bps
+    pub oracle_tolerance_bps: u32,
+    /// Whether oracle mismatches reject the claim instead of flagging it
+    pub reject_oracle_mismatch: bool,
+    /// Whether payouts exceeding the risk pool draw the shortfall from the treasury
+    pub emergency_draw_enabled: bool,
+    /// Whether new policies are rejected while the risk pool is below its minimum funding
+    pub require_funded_pool: bool,
+    /// Whether claims are rejected at processing time if their policy is no longer active
+    pub auto_reject_inactive_claims: bool,
+    /// Time a claim may stay pending before it is considered overdue, in seconds
+    pub claim_sla: u64,
+    /// Whether claims exceeding the risk pool are paid pro-rata instead of failing
+    pub partial_payout_enabled: bool,
+    /// Number of installments new and renewed policies pay their premium in
+    pub premium_installments: u32,
+    /// Upper bound on any single claim payout regardless of coverage (0 disables)
+    pub absolute_payout_cap: i128,
+    /// Time after policy start during which cancellation refunds the full premium, in seconds
+    pub free_cancel_window: u64,
+    /// Discount applied to the combined premium of policies bought as a bundle, in basis points
+    pub bundle_discount_bps: u32,
+    /// Time after policy start during which claims are rejected, in seconds
+    pub claim_waiting_period: u64,
+    /// Share of each new policy's premium collected upfront into the risk pool, in basis points
+    pub creation_risk_contribution_bps: u32,
+    /// Limit on total pending claim amounts as a multiple of the risk pool, in basis points (0 disables)
+    pub max_pending_exposure_bps: u32,
 }
 
 impl InsuranceConfig {
     /// Get default configuration
-    pub fn default(env: &Env) -> Self {
+    pub fn default(_env: &Env) -> Self {
         Self {
-            min_coverage: 100, // $1 minimum coverage
-            max_coverage: 1000000, // $10,000 maximum coverage
+            min_coverage: 1, // 1 token minimum coverage
+            max_coverage: 10000, // 10,000 tokens maximum coverage
             min_duration: 86400, // 1 day minimum
             max_duration: 31536000, // 365 days maximum
             claim_processing_approvals: 1, // Single approval required for Phase 1
+            flagged_claim_approvals: 2, // Second approval for previously flagged claimants
             max_risk_score: 80, // Maximum acceptable risk score
+            renewal_grace_period: 604800, // 7 days to renew after expiry
+            claim_cooldown: 86400, // 1 day between claims on a policy
+            settlement_delay: 86400, // 1 day fraud-review window before payout
+            utilization_threshold_bps: 8000, // Surcharge above 80% utilization
+            utilization_surcharge_bps: 2000, // +20% premium when over-utilized
+            min_premium_bps: 0, // No minimum premium ratio by default
+            max_pending_claims_per_policy: 3, // At most 3 claims in the queue per policy
+            max_evidence_items: 5, // Up to 5 documents per claim
+            refund_unused_premium: true, // Refund unused premium on total loss
+            oracle_tolerance_bps: 500, // Accept market values within 5% of the oracle
+            reject_oracle_mismatch: false, // Flag mismatches for manual review
+            emergency_draw_enabled: false, // Treasury draws require opting in
+            require_funded_pool: false, // Allow writing policies before the pool is funded
+            auto_reject_inactive_claims: true, // Don't approve claims on lapsed policies
+            claim_sla: 259200, // Claims should be processed within 3 days
+            partial_payout_enabled: false, // Under-funded payouts fail by default
+            premium_installments: 1, // Premium paid up front
+            absolute_payout_cap: 0, // No payout ceiling beyond coverage
+            free_cancel_window: 0, // No cooling-off period by default
+            bundle_discount_bps: 0, // Bundles priced like individual policies
+            claim_waiting_period: 0, // Claims allowed from the start of coverage
+            creation_risk_contribution_bps: 0, // New coverage adds no backing by default
+            max_pending_exposure_bps: 0, // Pending claims not limited by the pool by default
         }
     }
 }
@@ -66,27 +121,27 @@ impl InsuranceConfig {
 #[contractimpl]
 impl InsuranceContract {
     /// Initialize the insurance contract
-    pub fn __constructor(env: Env, admin: Address) {
-        let contract = Self {
-            policies: Map::new(env),
-            user_policies: Map::new(env),
-            claims: Map::new(env),
-            stats: PolicyStats {
-                active_policies: 0,
-                total_coverage: 0,
-                total_premiums: 0,
-                total_claims_paid: 0,
-                pending_claims: 0,
-            },
-            risk_pool_balance: 0,
-            premium_pool_balance: 0,
-            authorized_admins: Vec::from_array(env, [admin]),
-            authorized_processors: Vec::from_array(env, [admin]), // Admin can process claims initially
-            reentrancy_guard: ReentrancyGuard::new(),
-            config: InsuranceConfig::default(env),
-        };
-
-        contract.initialize(env);
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator address, also the contract owner
+    /// * `guardian` - Address allowed to pause the system
+    /// * `token_decimals` - Decimals of the premium/payout token (e.g. 7 for Stellar assets)
+    /// * `min_risk_pool` - Risk pool balance required before new coverage is backed
+    pub fn __constructor(env: Env, admin: Address, guardian: Address, token_decimals: u32, min_risk_pool: i128) {
+        InitGuard::initialize_once(&env);
+        SystemGuard::set_guardian(&env, &guardian);
+
+        if token_decimals > 18 || min_risk_pool < 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+        env.storage().instance().set(&Symbol::new(&env, "token_decimals"), &token_decimals);
+        env.storage().instance().set(&Symbol::new(&env, "min_risk_pool"), &min_risk_pool);
+
+        env.storage().instance().set(&Symbol::new(&env, "owner"), &admin);
+        env.storage().instance().set(&Symbol::new(&env, "authorized_admins"), &Vec::from_array(&env, [admin.clone()]));
+        // Admin can process claims initially
+        env.storage().instance().set(&Symbol::new(&env, "authorized_processors"), &Vec::from_array(&env, [admin]));
+        Self::initialize(&env);
     }
 
     /// Create a new insurance policy
@@ -94,281 +149,1574 @@ impl InsuranceContract {
     /// # Arguments
     /// * `policy_id` - Unique identifier for the policy
     /// * `params` - Policy creation parameters
-    pub fn create_policy(env: Env, policy_id: Bytes, params: CreatePolicyParams) {
+    ///
+    /// Returns a receipt with the stored policy's premium, expiry and effective coverage.
+    pub fn create_policy(env: Env, policy_id: Bytes, params: CreatePolicyParams) -> PolicyReceipt {
+        SystemGuard::require_not_paused(&env);
+
         // Validate inputs
         Self::validate_create_policy_params(&env, &params);
 
         // Check if policy already exists
-        if env.storage().instance().has(&Symbol::new(&env, "policies"), &policy_id) {
+        if env.storage().instance().has(&(Symbol::new(&env, "policies"), policy_id.clone())) {
             panic_with_error!(&env, ContractError::PolicyAlreadyExists);
         }
 
+        // In strict mode, don't sell coverage the risk pool can't back
+        if Self::get_config(env.clone()).require_funded_pool && !Self::is_solvent_to_write(env.clone()) {
+            panic_with_error!(&env, ContractError::InsufficientBalance);
+        }
+
+        // Don't concentrate more coverage on the pool than its cap allows
+        Self::require_pool_capacity(&env, &params.pool_id, params.coverage_amount);
+
         // Create and store the policy
         let mut policy = Policy::new(params.clone(), policy_id.clone(), &env);
+        policy.installments = Self::get_config(env.clone()).premium_installments;
+
+        // New coverage funds part of its own backing with an upfront share of the premium
+        let contribution = policy.premium * Self::get_config(env.clone()).creation_risk_contribution_bps as i128 / 10000;
+        policy.premium_paid_so_far = contribution;
 
         // Calculate premium based on risk score (already calculated by backend)
         let required_premium = params.premium;
 
         // Store the policy
-        env.storage().instance().set(&Symbol::new(&env, "policies"), &policy_id, &policy);
+        env.storage().instance().set(&(Symbol::new(&env, "policies"), policy_id.clone()), &policy);
 
         // Add to user's policy list
         let mut user_policies = env.storage().instance()
-            .get(&Symbol::new(&env, "user_policies"), &params.holder)
+            .get(&(Symbol::new(&env, "user_policies"), params.holder.clone()))
             .unwrap_or_else(|| Vec::new(&env));
 
-        user_policies.push_back(policy_id);
-        env.storage().instance().set(&Symbol::new(&env, "user_policies"), &params.holder, &user_policies);
+        user_policies.push_back(policy_id.clone());
+        env.storage().instance().set(&(Symbol::new(&env, "user_policies"), params.holder.clone()), &user_policies);
+
+        // Add to the index of every policy ever written
+        let mut policy_ids = Self::get_all_policy_ids(env.clone());
+        policy_ids.push_back(policy_id.clone());
+        env.storage().instance().set(&Symbol::new(&env, "policy_ids"), &policy_ids);
 
         // Update statistics
-        let mut stats = Self::get_stats(&env);
+        let mut stats = Self::get_stats(env.clone());
         stats.active_policies += 1;
         stats.total_coverage += policy.coverage_amount;
         stats.total_premiums += required_premium;
+        stats.funding.record(FundSource::Premiums, contribution);
         Self::set_stats(&env, stats);
+        Self::adjust_pool_exposure(&env, &policy.pool_id, policy.coverage_amount);
+
+        if contribution > 0 {
+            let risk_pool_balance = Self::get_risk_pool_balance(env.clone());
+            Self::set_risk_pool_balance(&env, risk_pool_balance + contribution);
+
+            env.events().publish(
+                (Symbol::new(&env, "risk_pool_contribution"), policy_id.clone()),
+                (contribution, risk_pool_balance + contribution),
+            );
+        }
 
         // Emit event
-        env.events().publish((
-            Symbol::new(&env, "policy_created"),
+        env.events().publish(
+            (Symbol::new(&env, "policy_created"), policy_id.clone()),
+            (
+                policy.holder.clone(),
+                policy.coverage_amount,
+                policy.premium,
+                policy.risk_score,
+            ),
+        );
+
+        PolicyReceipt {
             policy_id,
+            premium: policy.premium,
+            expiry_time: policy.expiry_time(),
+            effective_coverage: policy.effective_coverage(&env),
+        }
+    }
+
+    /// Create several policies for one holder in a single call
+    ///
+    /// The configured `bundle_discount_bps` is taken off the combined premium and
+    /// split across the policies in proportion to their premiums, with any rounding
+    /// left on the last policy. Policy IDs are generated for the holder. If any
+    /// policy in the bundle is invalid the whole call reverts. Requires the
+    /// holder's authorization.
+    ///
+    /// # Arguments
+    /// * `params` - Creation parameters for each policy in the bundle
+    pub fn create_policies_bundle(env: Env, params: Vec<CreatePolicyParams>) -> Vec<PolicyReceipt> {
+        if params.len() < 2 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let holder = params.get(0).unwrap().holder;
+        holder.require_auth();
+
+        let mut total_premium: i128 = 0;
+        for p in params.iter() {
+            if p.holder != holder || p.premium <= 0 {
+                panic_with_error!(&env, ContractError::InvalidInput);
+            }
+            total_premium += p.premium;
+        }
+
+        let total_discount = total_premium * Self::get_config(env.clone()).bundle_discount_bps as i128 / 10000;
+        let last = params.len() - 1;
+        let mut discount_applied: i128 = 0;
+        let mut receipts = Vec::new(&env);
+
+        for (i, mut p) in params.iter().enumerate() {
+            let share = if i as u32 == last {
+                total_discount - discount_applied
+            } else {
+                p.premium * total_discount / total_premium
+            };
+            discount_applied += share;
+            p.premium -= share;
+
+            let policy_id = generate_id(&env, Symbol::new(&env, "policy"), &holder, next_id_nonce(&env));
+            receipts.push_back(Self::create_policy(env.clone(), policy_id, p));
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "policy_bundle_created"), holder),
+            (receipts.len(), total_premium - total_discount, total_discount),
+        );
+
+        receipts
+    }
+
+    /// Renew a recently expired policy
+    ///
+    /// Coverage resumes from the renewal time. Policies expired for longer than
+    /// `renewal_grace_period` cannot be renewed and require a new policy.
+    ///
+    /// # Arguments
+    /// * `policy_id` - ID of the policy to renew
+    /// * `duration` - New policy duration in seconds
+    /// * `premium` - Premium for the renewed term
+    pub fn renew_policy(env: Env, policy_id: Bytes, duration: u64, premium: i128) {
+        SystemGuard::require_not_paused(&env);
+
+        let mut policy = Self::get_policy(env.clone(), policy_id.clone());
+        policy.holder.require_auth();
+
+        // Only active policies that have run out can be renewed
+        if policy.status != PolicyStatus::Active || !policy.is_expired(&env) {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        let config = Self::get_config(env.clone());
+        let expired_for = env.ledger().timestamp() - policy.expiry_time();
+        if expired_for >= config.renewal_grace_period {
+            panic_with_error!(&env, ContractError::PolicyExpired);
+        }
+
+        if duration == 0 || premium < 0 || env.ledger().timestamp().checked_add(duration).is_none() {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        Self::validate_duration(&env, duration);
+
+        policy.start_time = env.ledger().timestamp();
+        policy.duration = duration;
+        policy.premium = premium;
+        policy.premium_paid_so_far = 0;
+        policy.installments = Self::get_config(env.clone()).premium_installments;
+        env.storage().instance().set(&(Symbol::new(&env, "policies"), policy_id.clone()), &policy);
+
+        // Update statistics
+        let mut stats = Self::get_stats(env.clone());
+        stats.total_premiums += premium;
+        Self::set_stats(&env, stats);
+
+        // Emit event
+        env.events().publish(
+            (Symbol::new(&env, "policy_renewed"), policy_id),
+            (policy.holder.clone(), policy.expiry_time(), premium),
+        );
+    }
+
+    /// Cancel an active policy (holder only)
+    ///
+    /// Within `free_cancel_window` of the start the full premium paid is refunded;
+    /// afterwards the refund is pro-rated to the unused part of the term. Returns
+    /// the refunded amount.
+    ///
+    /// # Arguments
+    /// * `policy_id` - ID of the policy to cancel
+    pub fn cancel_policy(env: Env, policy_id: Bytes) -> i128 {
+        SystemGuard::require_not_paused(&env);
+
+        let mut policy = Self::get_policy(env.clone(), policy_id.clone());
+        policy.holder.require_auth();
+
+        if policy.status != PolicyStatus::Active || policy.has_pending_claims() {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        // Never refund more than was paid or than the premium pool holds
+        let in_free_window = env.ledger().timestamp() - policy.start_time < Self::get_config(env.clone()).free_cancel_window;
+        let entitled = if in_free_window {
+            policy.premium_paid_so_far
+        } else {
+            policy.unused_premium(&env).min(policy.premium_paid_so_far)
+        };
+        let refund = entitled.min(Self::get_premium_pool_balance(env.clone())).max(0);
+        if refund > 0 {
+            let premium_pool_balance = Self::get_premium_pool_balance(env.clone());
+            Self::set_premium_pool_balance(&env, premium_pool_balance - refund);
+        }
+
+        policy.status = PolicyStatus::Cancelled;
+        Self::close_policy(&env, &policy_id, &policy);
+
+        // In production, this would transfer the refund to the holder
+        if refund > 0 {
+            env.events().publish(
+                (Symbol::new(&env, "premium_refunded"), policy_id.clone()),
+                (policy.holder.clone(), refund),
+            );
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "policy_cancelled"), policy_id),
+            (policy.holder, in_free_window),
+        );
+
+        refund
+    }
+
+    /// Mark a policy expired once its renewal grace period has passed
+    ///
+    /// Anyone can call this to release the policy's exposure.
+    ///
+    /// # Arguments
+    /// * `policy_id` - ID of the policy to expire
+    pub fn expire_policy(env: Env, policy_id: Bytes) {
+        SystemGuard::require_not_paused(&env);
+
+        let mut policy = Self::get_policy(env.clone(), policy_id.clone());
+
+        if policy.status != PolicyStatus::Active || !policy.is_expired(&env) || policy.has_pending_claims() {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        // Policies can still be renewed during the grace period
+        let expired_for = env.ledger().timestamp() - policy.expiry_time();
+        if expired_for < Self::get_config(env.clone()).renewal_grace_period {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        policy.status = PolicyStatus::Expired;
+        Self::close_policy(&env, &policy_id, &policy);
+
+        env.events().publish(
+            (Symbol::new(&env, "policy_expired"), policy_id),
             policy.holder,
-            policy.coverage_amount,
-            policy.premium,
-            policy.risk_score,
-        ));
+        );
+    }
+
+    /// Get the total coverage of active policies backed by a pool
+    pub fn get_pool_exposure(env: Env, pool_id: Bytes) -> i128 {
+        env.storage().instance()
+            .get(&(Symbol::new(&env, "pool_exposure"), pool_id.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Get the maximum total active coverage a pool may back (0 means uncapped)
+    pub fn get_pool_coverage_cap(env: Env, pool_id: Bytes) -> i128 {
+        env.storage().instance()
+            .get(&(Symbol::new(&env, "pool_coverage_caps"), pool_id.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Set the coverage cap for a pool (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator setting the cap
+    /// * `pool_id` - Pool to cap
+    /// * `cap` - Maximum total active coverage, or 0 to remove the cap
+    pub fn update_pool_coverage_cap(env: Env, admin: Address, pool_id: Bytes, cap: i128) {
+        Self::require_admin(&env, admin.clone());
+
+        if cap < 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        env.storage().instance().set(&(Symbol::new(&env, "pool_coverage_caps"), pool_id.clone()), &cap);
+
+        env.events().publish(
+            (Symbol::new(&env, "pool_coverage_cap_updated"), admin),
+            (pool_id, cap),
+        );
     }
 
     /// Pay premium for a policy
     ///
+    /// Accepts partial payments; the premium is settled once the total paid for
+    /// the current term reaches the policy premium. Claims are only accepted while
+    /// payments keep up with the installment schedule.
+    ///
     /// # Arguments
     /// * `policy_id` - ID of the policy to pay premium for
     /// * `amount` - Premium amount to pay
     pub fn pay_premium(env: Env, policy_id: Bytes, amount: i128) {
+        SystemGuard::require_not_paused(&env);
+
         // Get the policy
-        let mut policy = Self::get_policy(&env, &policy_id);
+        let mut policy = Self::get_policy(env.clone(), policy_id.clone());
 
         // Check if policy is active
         if !policy.is_active(&env) {
             panic_with_error!(&env, ContractError::InvalidState);
         }
 
-        // Validate premium amount
-        if amount != policy.premium {
+        // Validate premium amount, never accepting more than the outstanding premium
+        if amount <= 0 || policy.premium_paid_so_far + amount > policy.premium {
             panic_with_error!(&env, ContractError::InvalidInput);
         }
 
+        policy.premium_paid_so_far += amount;
+        env.storage().instance().set(&(Symbol::new(&env, "policies"), policy_id.clone()), &policy);
+
         // Update premium pool balance
-        let current_balance = Self::get_premium_pool_balance(&env);
+        let current_balance = Self::get_premium_pool_balance(env.clone());
         let new_balance = current_balance + amount;
         Self::set_premium_pool_balance(&env, new_balance);
 
         // Emit event
-        env.events().publish((
-            Symbol::new(&env, "premium_paid"),
-            policy_id,
-            policy.holder,
-            amount,
-            new_balance,
-        ));
+        env.events().publish(
+            (Symbol::new(&env, "premium_paid"), policy_id.clone()),
+            (policy.holder.clone(), amount, new_balance),
+        );
+
+        if policy.is_premium_paid() {
+            env.events().publish(
+                (Symbol::new(&env, "premium_settled"), policy_id),
+                (policy.holder, policy.premium_paid_so_far),
+            );
+        }
     }
 
     /// Submit an insurance claim
     ///
+    /// Only the policy holder or its designated beneficiary may file a claim.
+    ///
     /// # Arguments
+    /// * `claimant` - Policy holder or beneficiary filing the claim
     /// * `claim_id` - Unique identifier for the claim
     /// * `policy_id` - ID of the policy being claimed
     /// * `amount` - Claim amount
     /// * `evidence` - Evidence supporting the claim
-    pub fn submit_claim(env: Env, claim_id: Bytes, policy_id: Bytes, amount: i128, evidence: ClaimEvidence) {
+    /// * `reference` - Optional off-chain reference echoed in claim events
+    pub fn submit_claim(
+        env: Env,
+        claimant: Address,
+        claim_id: Bytes,
+        policy_id: Bytes,
+        amount: i128,
+        evidence: ClaimEvidence,
+        reference: Option<Symbol>,
+    ) {
+        SystemGuard::require_not_paused(&env);
+
         // Get the policy
-        let policy = Self::get_policy(&env, &policy_id);
+        let mut policy = Self::get_policy(env.clone(), policy_id.clone());
+
+        // Only the holder or designated beneficiary can claim against a policy
+        if claimant != policy.holder && Some(claimant.clone()) != policy.beneficiary {
+            panic_with_error!(&env, ContractError::Unauthorized);
+        }
+        claimant.require_auth();
 
         // Check if policy is active
         if !policy.is_active(&env) {
             panic_with_error!(&env, ContractError::InvalidState);
         }
 
+        // Coverage only applies while premium payments are up to date
+        if !policy.is_paid_up(&env) {
+            panic_with_error!(&env, ContractError::InsufficientBalance);
+        }
+
         // Validate claim amount doesn't exceed effective coverage
-        if amount > policy.effective_coverage() {
+        let effective_coverage = policy.effective_coverage(&env);
+        if amount > effective_coverage {
             panic_with_error!(&env, ContractError::InvalidClaimAmount);
         }
 
+        let config = Self::get_config(env.clone());
+
+        // Deter buying cover right before a known loss
+        if policy.is_in_waiting_period(&env, config.claim_waiting_period) {
+            panic_with_error!(&env, ContractError::WaitingPeriodActive);
+        }
+
+        // Reject claims submitted too soon after the previous one on this policy
+        if policy.is_in_claim_cooldown(&env, config.claim_cooldown) {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        // Limit how many claims a single policy can have in the queue
+        if Self::count_pending_claims(&env, &policy_id) >= config.max_pending_claims_per_policy {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        // Don't let the claims queue commit more than the pool can back
+        if config.max_pending_exposure_bps > 0 {
+            let exposure_limit = Self::get_risk_pool_balance(env.clone()) * config.max_pending_exposure_bps as i128 / 10000;
+            if Self::total_pending_claim_amount(&env) + amount > exposure_limit {
+                panic_with_error!(&env, ContractError::InsufficientBalance);
+            }
+        }
+
         // Check if claim already exists
-        if env.storage().instance().has(&Symbol::new(&env, "claims"), &claim_id) {
+        if env.storage().instance().has(&(Symbol::new(&env, "claims"), claim_id.clone())) {
             panic_with_error!(&env, ContractError::InvalidInput);
         }
 
+        // Verify market-loss evidence against the price oracle, if one is configured
+        let oracle_flagged = Self::verify_market_loss(&env, &evidence, &config);
+
+        // Reserve the claim amount against unreserved risk pool funds
+        if Self::get_available_risk_pool_balance(env.clone()) < amount {
+            panic_with_error!(&env, ContractError::InsufficientBalance);
+        }
+        let reserved_balance = Self::get_reserved_balance(env.clone());
+        Self::set_reserved_balance(&env, reserved_balance + amount);
+
         // Create and store the claim
-        let claim = Claim::new(
+        let mut claim = Claim::new(
             claim_id.clone(),
             policy_id.clone(),
-            policy.holder,
+            claimant.clone(),
             amount,
             evidence,
+            reference.clone(),
             &env,
         );
+        claim.oracle_flagged = oracle_flagged;
+        claim.effective_coverage = effective_coverage;
+
+        // Claimants with prior fraud flags need the stricter approval threshold
+        claim.required_approvals = if Self::get_fraud_flags(env.clone(), claim.claimant.clone()) > 0 {
+            config.flagged_claim_approvals.max(config.claim_processing_approvals)
+        } else {
+            config.claim_processing_approvals
+        };
+
+        env.storage().instance().set(&(Symbol::new(&env, "claims"), claim_id.clone()), &claim);
+
+        // Add to the policy's claim index
+        let mut policy_claims = Self::get_policy_claims(env.clone(), policy_id.clone());
+        policy_claims.push_back(claim_id.clone());
+        env.storage().instance().set(&(Symbol::new(&env, "policy_claims"), policy_id.clone()), &policy_claims);
+
+        // Add to the pending queue, which stays ordered by submission time
+        let mut pending_claim_ids = Self::get_pending_claim_ids(&env);
+        pending_claim_ids.push_back(claim_id.clone());
+        env.storage().instance().set(&Symbol::new(&env, "pending_claim_ids"), &pending_claim_ids);
 
-        env.storage().instance().set(&Symbol::new(&env, "claims"), &claim_id, &claim);
+        // Track pending claims and the claim time on the policy
+        policy.pending_claims += 1;
+        policy.last_claim_at = Some(env.ledger().timestamp());
+        env.storage().instance().set(&(Symbol::new(&env, "policies"), policy_id.clone()), &policy);
 
         // Update statistics
-        let mut stats = Self::get_stats(&env);
+        let mut stats = Self::get_stats(env.clone());
         stats.pending_claims += 1;
         Self::set_stats(&env, stats);
 
         // Emit event
-        env.events().publish((
-            Symbol::new(&env, "claim_submitted"),
-            claim_id,
-            policy_id,
-            policy.holder,
-            amount,
-        ));
+        env.events().publish(
+            (Symbol::new(&env, "claim_submitted"), claim_id.clone()),
+            (policy_id.clone(), claimant, amount, reference.clone()),
+        );
+
+        if oracle_flagged {
+            env.events().publish(
+                (Symbol::new(&env, "claim_oracle_mismatch"), claim_id),
+                (policy_id, reference),
+            );
+        }
+    }
+
+    /// Attach another evidence item to a pending claim (claimant only)
+    ///
+    /// # Arguments
+    /// * `claim_id` - ID of the pending claim
+    /// * `evidence` - Additional evidence supporting the claim
+    pub fn add_claim_evidence(env: Env, claim_id: Bytes, evidence: ClaimEvidence) {
+        SystemGuard::require_not_paused(&env);
+
+        let mut claim = Self::get_claim(env.clone(), claim_id.clone());
+        claim.claimant.require_auth();
+
+        // Evidence can only be added before the claim is processed
+        if !claim.is_pending() {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        if claim.evidence.len() >= Self::get_config(env.clone()).max_evidence_items {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        claim.evidence.push_back(evidence);
+        env.storage().instance().set(&(Symbol::new(&env, "claims"), claim_id.clone()), &claim);
+
+        env.events().publish(
+            (Symbol::new(&env, "claim_evidence_added"), claim_id),
+            claim.evidence.len(),
+        );
     }
 
     /// Process a claim (approve or reject)
     ///
+    /// If `auto_reject_inactive_claims` is set and the claim's policy is no longer
+    /// active, the claim is rejected with `PolicyInactive` whatever the decision.
+    ///
     /// # Arguments
     /// * `claim_id` - ID of the claim to process
     /// * `approved` - Whether to approve the claim
     /// * `processor` - Address of the claim processor
     /// * `reason` - Reason for the decision
-    pub fn process_claim(env: Env, claim_id: Bytes, approved: bool, processor: Address, reason: Symbol) {
+    /// * `rejection_reason` - Categorized reason, used when rejecting (defaults to `Other`)
+    pub fn process_claim(
+        env: Env,
+        claim_id: Bytes,
+        approved: bool,
+        processor: Address,
+        reason: Symbol,
+        rejection_reason: Option<RejectionReason>,
+    ) {
+        SystemGuard::require_not_paused(&env);
+
         // Check if processor is authorized
-        let processors = Self::get_authorized_processors(&env);
+        let processors = Self::get_authorized_processors(env.clone());
         if !processors.contains(&processor) {
             panic_with_error!(&env, ContractError::Unauthorized);
         }
+        processor.require_auth();
 
         // Get the claim
-        let mut claim = Self::get_claim(&env, &claim_id);
+        let mut claim = Self::get_claim(env.clone(), claim_id.clone());
 
         // Check if claim is pending
         if !claim.is_pending() {
             panic_with_error!(&env, ContractError::InvalidState);
         }
 
+        // Claims whose policy lapsed before processing are rejected regardless of the decision
+        let policy_lapsed = Self::get_config(env.clone()).auto_reject_inactive_claims
+            && !Self::get_policy(env.clone(), claim.policy_id.clone()).is_active(&env);
+        let (approved, reason, rejection_reason) = if policy_lapsed {
+            (false, Symbol::new(&env, "policy_inactive"), Some(RejectionReason::PolicyInactive))
+        } else {
+            (approved, reason, rejection_reason)
+        };
+
+        // Approvals below the claim's threshold are recorded and the claim stays pending
         if approved {
-            // Approve the claim
-            claim.approve(processor.clone(), reason, &env);
+            if claim.approvals.contains(&processor) {
+                panic_with_error!(&env, ContractError::InvalidState);
+            }
 
-            // Check if risk pool has sufficient balance
-            let risk_pool_balance = Self::get_risk_pool_balance(&env);
-            if risk_pool_balance < claim.amount {
-                panic_with_error!(&env, ContractError::InsufficientBalance);
+            if !claim.add_approval(processor.clone()) {
+                env.storage().instance().set(&(Symbol::new(&env, "claims"), claim_id.clone()), &claim);
+                env.events().publish(
+                    (Symbol::new(&env, "claim_approval_recorded"), claim_id),
+                    (processor, claim.approvals.len(), claim.required_approvals),
+                );
+                return;
             }
+        }
+
+        // The claim is no longer pending on its policy
+        let mut policy = Self::get_policy(env.clone(), claim.policy_id.clone());
+        policy.pending_claims = policy.pending_claims.saturating_sub(1);
+        env.storage().instance().set(&(Symbol::new(&env, "policies"), claim.policy_id.clone()), &policy);
+
+        // Or in the pending queue
+        let mut pending_claim_ids = Self::get_pending_claim_ids(&env);
+        if let Some(index) = pending_claim_ids.first_index_of(&claim_id) {
+            pending_claim_ids.remove(index);
+        }
+        env.storage().instance().set(&Symbol::new(&env, "pending_claim_ids"), &pending_claim_ids);
 
-            // Update risk pool balance
-            let new_balance = risk_pool_balance - claim.amount;
-            Self::set_risk_pool_balance(&env, new_balance);
+        if approved {
+            // Approve the claim; funds stay reserved until payout
+            claim.approve(processor.clone(), reason.clone(), Self::get_config(env.clone()).settlement_delay, &env);
 
             // Update statistics
-            let mut stats = Self::get_stats(&env);
-            stats.total_claims_paid += claim.amount;
+            let mut stats = Self::get_stats(env.clone());
             stats.pending_claims -= 1;
             Self::set_stats(&env, stats);
 
             // Emit event
-            env.events().publish((
-                Symbol::new(&env, "claim_approved"),
-                claim_id,
-                claim.amount,
-                processor,
-                reason,
-            ));
+            env.events().publish(
+                (Symbol::new(&env, "claim_approved"), claim_id.clone()),
+                (
+                    claim.amount,
+                    processor,
+                    reason,
+                    claim.payable_after,
+                    claim.reference.clone(),
+                ),
+            );
         } else {
             // Reject the claim
-            claim.reject(processor, reason, &env);
+            let rejection_reason = rejection_reason.unwrap_or(RejectionReason::Other);
+            claim.reject(processor.clone(), reason.clone(), rejection_reason, &env);
+
+            // Release the funds reserved at submission
+            Self::release_reserve(&env, claim.amount);
 
             // Update statistics
-            let mut stats = Self::get_stats(&env);
+            let mut stats = Self::get_stats(env.clone());
             stats.pending_claims -= 1;
+            stats.rejections.record(rejection_reason);
             Self::set_stats(&env, stats);
 
+            // Suspected fraud raises the bar for the claimant's future claims
+            if rejection_reason == RejectionReason::FraudSuspected {
+                let mut fraud_flags = Self::get_all_fraud_flags(&env);
+                let flags = fraud_flags.get(claim.claimant.clone()).unwrap_or(0) + 1;
+                fraud_flags.set(claim.claimant.clone(), flags);
+                env.storage().instance().set(&Symbol::new(&env, "fraud_flags"), &fraud_flags);
+
+                env.events().publish(
+                    (Symbol::new(&env, "fraud_flag_recorded"), claim.claimant.clone()),
+                    flags,
+                );
+            }
+
             // Emit event
-            env.events().publish((
-                Symbol::new(&env, "claim_rejected"),
-                claim_id,
-                processor,
-                reason,
-            ));
+            env.events().publish(
+                (Symbol::new(&env, "claim_rejected"), claim_id.clone()),
+                (processor, reason, rejection_reason, claim.reference.clone()),
+            );
         }
 
         // Store updated claim
-        env.storage().instance().set(&Symbol::new(&env, "claims"), &claim_id, &claim);
+        env.storage().instance().set(&(Symbol::new(&env, "claims"), claim_id.clone()), &claim);
     }
 
-    /// Get policy information
-    pub fn get_policy(env: Env, policy_id: Bytes) -> Policy {
-        env.storage().instance()
-            .get(&Symbol::new(&env, "policies"), &policy_id)
-            .unwrap_or_else(|| panic_with_error!(&env, ContractError::PolicyNotFound))
-    }
+    /// Pay out an approved claim once its settlement delay has passed
+    ///
+    /// # Arguments
+    /// * `claim_id` - ID of the approved claim to pay
+    pub fn pay_claim(env: Env, claim_id: Bytes) {
+        SystemGuard::require_not_paused(&env);
 
-    /// Get claim information
-    pub fn get_claim(env: Env, claim_id: Bytes) -> Claim {
-        env.storage().instance()
-            .get(&Symbol::new(&env, "claims"), &claim_id)
-            .unwrap_or_else(|| panic!("Claim not found"))
-    }
+        let claim = Self::get_claim(env.clone(), claim_id.clone());
 
-    /// Get all policies for a user
-    pub fn get_user_policies(env: Env, user: Address) -> Vec<Bytes> {
-        env.storage().instance()
-            .get(&Symbol::new(&env, "user_policies"), &user)
-            .unwrap_or_else(|| Vec::new(&env))
-    }
+        if claim.status != ClaimStatus::Approved || !claim.is_payable(&env) {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
 
-    /// Get global statistics
-    pub fn get_stats(env: Env) -> PolicyStats {
-        env.storage().instance()
-            .get(&Symbol::new(&env, "stats"))
-            .unwrap_or_else(|| PolicyStats {
-                active_policies: 0,
-                total_coverage: 0,
-                total_premiums: 0,
-                total_claims_paid: 0,
-                pending_claims: 0,
-            })
-    }
+        // Never pay out to an unrecoverable address
+        let policy = Self::get_policy(env.clone(), claim.policy_id.clone());
+        require_nonzero_address(&env, &policy.payout_address());
 
-    /// Add funds to the risk pool (admin only)
-    pub fn fund_risk_pool(env: Env, admin: Address, amount: i128) {
-        Self::require_admin(&env, admin);
+        // The pool only has to cover the net payout after deductible and caps
+        let net_amount = Self::capped_payout(&env, &policy, claim.effective_coverage, claim.amount);
 
-        let current_balance = Self::get_risk_pool_balance(&env);
-        let new_balance = current_balance + amount;
-        Self::set_risk_pool_balance(&env, new_balance);
+        // Top up an exhausted risk pool from the treasury emergency fund if enabled
+        let mut risk_pool_balance = Self::get_risk_pool_balance(env.clone());
+        if risk_pool_balance < net_amount && Self::get_config(env.clone()).emergency_draw_enabled {
+            Self::draw_from_treasury(&env, net_amount - risk_pool_balance);
+            risk_pool_balance = Self::get_risk_pool_balance(env.clone());
+        }
 
-        env.events().publish((
-            Symbol::new(&env, "risk_pool_funded"),
-            admin,
-            amount,
-            new_balance,
-        ));
-    }
+        // Check if risk pool has sufficient balance, unless partial payouts are allowed
+        if risk_pool_balance < net_amount
+            && (!Self::get_config(env.clone()).partial_payout_enabled || risk_pool_balance <= 0)
+        {
+            panic_with_error!(&env, ContractError::InsufficientBalance);
+        }
 
-    /// Get current risk pool balance
-    pub fn get_risk_pool_balance(env: Env) -> i128 {
-        env.storage().instance()
-            .get(&Symbol::new(&env, "risk_pool_balance"))
-            .unwrap_or(0)
-    }
+        // Pay as much of the net amount as the pool holds; the rest stays reserved
+        let payout = net_amount.min(risk_pool_balance);
+        Self::set_risk_pool_balance(&env, risk_pool_balance - payout);
 
-    /// Get current premium pool balance
-    pub fn get_premium_pool_balance(env: Env) -> i128 {
-        env.storage().instance()
-            .get(&Symbol::new(&env, "premium_pool_balance"))
-            .unwrap_or(0)
+        Self::settle_claim(&env, claim, payout, net_amount - payout);
     }
 
-    /// Get authorized administrators
-    pub fn get_authorized_admins(env: Env) -> Vec<Address> {
-        env.storage().instance()
-            .get(&Symbol::new(&env, "authorized_admins"))
-            .unwrap_or_else(|| Vec::new(&env))
-    }
+    /// Pay several approved claims with a single risk pool deduction (processor only)
+    ///
+    /// Claims are taken in order while the pool covers their full payout. Claims
+    /// that aren't payable, don't fit in what remains of the pool, or repeat an
+    /// ID already selected, are skipped.
+    ///
+    /// # Arguments
+    /// * `processor` - Authorized claim processor
+    /// * `claim_ids` - IDs of the approved claims to pay
+    pub fn pay_claims_batch(env: Env, processor: Address, claim_ids: Vec<Bytes>) -> BatchPayoutSummary {
+        SystemGuard::require_not_paused(&env);
 
-    /// Get authorized claim processors
+        if !Self::get_authorized_processors(env.clone()).contains(&processor) {
+            panic_with_error!(&env, ContractError::Unauthorized);
+        }
+        processor.require_auth();
+
+        let risk_pool_balance = Self::get_risk_pool_balance(env.clone());
+        let mut summary = BatchPayoutSummary::new(&env);
+        let mut payable = Vec::new(&env);
+
+        // Select claims against the pool before touching any state
+        for claim_id in claim_ids.iter() {
+            // A repeated ID would otherwise be settled twice from the same status
+            if summary.paid.contains(&claim_id) {
+                summary.skipped.push_back(claim_id);
+                continue;
+            }
+
+            let claim: Option<Claim> = env.storage().instance().get(&(Symbol::new(&env, "claims"), claim_id.clone()));
+            let claim = match claim {
+                Some(claim) if claim.status == ClaimStatus::Approved && claim.is_payable(&env) => claim,
+                _ => {
+                    summary.skipped.push_back(claim_id);
+                    continue;
+                }
+            };
+
+            let policy = Self::get_policy(env.clone(), claim.policy_id.clone());
+            let payout = Self::capped_payout(&env, &policy, claim.effective_coverage, claim.amount);
+            if is_zero_address(&env, &policy.payout_address())
+                || summary.total_paid + payout > risk_pool_balance
+            {
+                summary.skipped.push_back(claim_id);
+                continue;
+            }
+
+            summary.total_paid += payout;
+            summary.paid.push_back(claim_id);
+            payable.push_back((claim, payout));
+        }
+
+        Self::set_risk_pool_balance(&env, risk_pool_balance - summary.total_paid);
+
+        for (claim, payout) in payable.iter() {
+            Self::settle_claim(&env, claim, payout, 0);
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "claims_batch_paid"), processor),
+            (summary.paid.len(), summary.skipped.len(), summary.total_paid),
+        );
+
+        summary
+    }
+
+    /// Collect the outstanding remainder of a partially paid claim (claimant only)
+    ///
+    /// Pays as much of the remainder as the risk pool currently holds; the claim
+    /// stays partially paid until the remainder reaches zero. Returns the amount paid.
+    ///
+    /// # Arguments
+    /// * `claim_id` - ID of the partially paid claim
+    pub fn claim_remainder(env: Env, claim_id: Bytes) -> i128 {
+        SystemGuard::require_not_paused(&env);
+
+        let mut claim = Self::get_claim(env.clone(), claim_id.clone());
+
+        if claim.status != ClaimStatus::PartiallyPaid {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        claim.claimant.require_auth();
+
+        let mut policy = Self::get_policy(env.clone(), claim.policy_id.clone());
+        require_nonzero_address(&env, &policy.payout_address());
+
+        let risk_pool_balance = Self::get_risk_pool_balance(env.clone());
+        if risk_pool_balance <= 0 {
+            panic_with_error!(&env, ContractError::InsufficientBalance);
+        }
+
+        let payout = claim.unpaid_remainder.min(risk_pool_balance);
+        Self::release_reserve(&env, payout);
+        Self::set_risk_pool_balance(&env, risk_pool_balance - payout);
+
+        let unpaid_remainder = claim.unpaid_remainder - payout;
+        if unpaid_remainder > 0 {
+            claim.mark_as_partially_paid(&env, unpaid_remainder);
+        } else {
+            claim.mark_as_paid(&env);
+        }
+        env.storage().instance().set(&(Symbol::new(&env, "claims"), claim_id.clone()), &claim);
+
+        policy.claimed_amount += payout;
+        env.storage().instance().set(&(Symbol::new(&env, "policies"), claim.policy_id.clone()), &policy);
+
+        let mut stats = Self::get_stats(env.clone());
+        stats.total_claims_paid += payout;
+        Self::set_stats(&env, stats);
+
+        // In production, this would transfer funds to the payee
+        env.events().publish(
+            (Symbol::new(&env, "claim_remainder_paid"), claim_id),
+            (
+                policy.payout_address(),
+                Self::quote_payout(env.clone(), payout),
+                unpaid_remainder,
+            ),
+        );
+
+        payout
+    }
+
+    /// Project the net payout for a hypothetical claim on a policy
+    ///
+    /// Applies the remaining coverage, the deductible and the absolute payout cap
+    /// without changing state.
+    ///
+    /// # Arguments
+    /// * `policy_id` - ID of the policy
+    /// * `claim_amount` - Amount that would be claimed
+    pub fn project_payout(env: Env, policy_id: Bytes, claim_amount: i128) -> i128 {
+        if claim_amount < 0 {
+            panic_with_error!(&env, ContractError::InvalidClaimAmount);
+        }
+
+        let policy = Self::get_policy(env.clone(), policy_id.clone());
+        Self::capped_payout(&env, &policy, policy.effective_coverage(&env), claim_amount)
+    }
+
+    /// Draw from the treasury emergency fund into the risk pool (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator triggering the draw
+    /// * `amount` - Amount to move into the risk pool
+    pub fn request_emergency_funds(env: Env, admin: Address, amount: i128) {
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_admin(&env, admin);
+
+        if amount <= 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        Self::draw_from_treasury(&env, amount);
+    }
+
+    /// Veto an approved claim before it is paid (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator vetoing the claim
+    /// * `claim_id` - ID of the approved, unpaid claim
+    /// * `reason` - Reason for the veto
+    pub fn veto_claim(env: Env, admin: Address, claim_id: Bytes, reason: Symbol) {
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_admin(&env, admin.clone());
+
+        let mut claim = Self::get_claim(env.clone(), claim_id.clone());
+
+        // Only approved claims that have not been paid can be vetoed
+        if claim.status != ClaimStatus::Approved {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        claim.reject(admin.clone(), reason.clone(), RejectionReason::Other, &env);
+        claim.payable_after = None;
+        env.storage().instance().set(&(Symbol::new(&env, "claims"), claim_id.clone()), &claim);
+
+        // Release the funds reserved at submission
+        Self::release_reserve(&env, claim.amount);
+
+        let mut stats = Self::get_stats(env.clone());
+        stats.rejections.record(RejectionReason::Other);
+        Self::set_stats(&env, stats);
+
+        env.events().publish(
+            (Symbol::new(&env, "claim_vetoed"), claim_id),
+            (admin, reason, claim.reference),
+        );
+    }
+
+    /// Set or clear the payout beneficiary of a policy (holder only)
+    ///
+    /// # Arguments
+    /// * `policy_id` - ID of the policy to update
+    /// * `beneficiary` - New beneficiary, or `None` to pay the holder
+    pub fn set_beneficiary(env: Env, policy_id: Bytes, beneficiary: Option<Address>) {
+        SystemGuard::require_not_paused(&env);
+
+        let mut policy = Self::get_policy(env.clone(), policy_id.clone());
+        policy.holder.require_auth();
+
+        // Payout target must not change while a claim is in flight
+        if policy.has_pending_claims() {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        if let Some(beneficiary) = &beneficiary {
+            require_nonzero_address(&env, beneficiary);
+        }
+
+        policy.beneficiary = beneficiary.clone();
+        env.storage().instance().set(&(Symbol::new(&env, "policies"), policy_id.clone()), &policy);
+
+        env.events().publish(
+            (Symbol::new(&env, "beneficiary_updated"), policy_id),
+            (policy.holder, beneficiary),
+        );
+    }
+
+    /// Transfer a policy to a new holder (current holder only)
+    ///
+    /// # Arguments
+    /// * `policy_id` - ID of the policy to transfer
+    /// * `new_holder` - Address taking over the policy
+    pub fn transfer_policy(env: Env, policy_id: Bytes, new_holder: Address) {
+        SystemGuard::require_not_paused(&env);
+
+        let mut policy = Self::get_policy(env.clone(), policy_id.clone());
+        let old_holder = policy.holder.clone();
+        old_holder.require_auth();
+
+        if new_holder == old_holder {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+        require_nonzero_address(&env, &new_holder);
+
+        // Ownership must not change while a claim is in flight
+        if policy.has_pending_claims() {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        policy.holder = new_holder.clone();
+        env.storage().instance().set(&(Symbol::new(&env, "policies"), policy_id.clone()), &policy);
+
+        // Move the policy between the holders' policy lists
+        let mut old_policies = Self::get_user_policies(env.clone(), old_holder.clone());
+        let mut i = 0;
+        while i < old_policies.len() {
+            if old_policies.get(i).unwrap() == policy_id {
+                old_policies.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+        env.storage().instance().set(&(Symbol::new(&env, "user_policies"), old_holder.clone()), &old_policies);
+
+        let mut new_policies = Self::get_user_policies(env.clone(), new_holder.clone());
+        new_policies.push_back(policy_id.clone());
+        env.storage().instance().set(&(Symbol::new(&env, "user_policies"), new_holder.clone()), &new_policies);
+
+        env.events().publish(
+            (Symbol::new(&env, "policy_transferred"), policy_id),
+            (old_holder, new_holder),
+        );
+    }
+
+    /// Move a policy to a different registered yield pool (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator performing the migration
+    /// * `policy_id` - ID of the policy to migrate
+    /// * `new_pool_id` - Registered pool to associate the policy with
+    pub fn migrate_policy_pool(env: Env, admin: Address, policy_id: Bytes, new_pool_id: Bytes) {
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_admin(&env, admin.clone());
+
+        if !Self::get_registered_pools(env.clone()).contains(&new_pool_id) {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let mut policy = Self::get_policy(env.clone(), policy_id.clone());
+        let old_pool_id = policy.pool_id.clone();
+        policy.pool_id = new_pool_id.clone();
+        env.storage().instance().set(&(Symbol::new(&env, "policies"), policy_id.clone()), &policy);
+
+        // Move the policy's coverage to the new pool's exposure
+        if policy.status == PolicyStatus::Active {
+            Self::require_pool_capacity(&env, &new_pool_id, policy.coverage_amount);
+            Self::adjust_pool_exposure(&env, &old_pool_id, -policy.coverage_amount);
+            Self::adjust_pool_exposure(&env, &new_pool_id, policy.coverage_amount);
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "policy_pool_migrated"), policy_id),
+            (old_pool_id, new_pool_id, admin),
+        );
+    }
+
+    /// Get policy information
+    pub fn get_policy(env: Env, policy_id: Bytes) -> Policy {
+        env.storage().instance()
+            .get(&(Symbol::new(&env, "policies"), policy_id.clone()))
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::PolicyNotFound))
+    }
+
+    /// Get claim information
+    pub fn get_claim(env: Env, claim_id: Bytes) -> Claim {
+        env.storage().instance()
+            .get(&(Symbol::new(&env, "claims"), claim_id.clone()))
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound))
+    }
+
+    /// Get all claims filed against a policy
+    pub fn get_policy_claims(env: Env, policy_id: Bytes) -> Vec<Bytes> {
+        env.storage().instance()
+            .get(&(Symbol::new(&env, "policy_claims"), policy_id.clone()))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get all policies for a user
+    pub fn get_user_policies(env: Env, user: Address) -> Vec<Bytes> {
+        env.storage().instance()
+            .get(&(Symbol::new(&env, "user_policies"), user.clone()))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get the IDs of every policy ever created, in creation order
+    pub fn get_all_policy_ids(env: Env) -> Vec<Bytes> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "policy_ids"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Rebuild a user's policy index from the policies they actually hold (admin only)
+    ///
+    /// Repairs drift in `user_policies` by scanning every policy for `user` as holder.
+    /// Returns the rebuilt list.
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator triggering the rebuild
+    /// * `user` - Address whose index is rebuilt
+    pub fn rebuild_user_index(env: Env, admin: Address, user: Address) -> Vec<Bytes> {
+        Self::require_admin(&env, admin.clone());
+
+        let mut user_policies = Vec::new(&env);
+        for policy_id in Self::get_all_policy_ids(env.clone()).iter() {
+            if Self::get_policy(env.clone(), policy_id.clone()).holder == user {
+                user_policies.push_back(policy_id);
+            }
+        }
+
+        env.storage().instance().set(&(Symbol::new(&env, "user_policies"), user.clone()), &user_policies);
+
+        env.events().publish(
+            (Symbol::new(&env, "user_index_rebuilt"), admin),
+            (user, user_policies.len()),
+        );
+
+        user_policies
+    }
+
+    /// Get global statistics
+    pub fn get_stats(env: Env) -> PolicyStats {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "stats"))
+            .unwrap_or_else(|| PolicyStats {
+                active_policies: 0,
+                total_coverage: 0,
+                total_premiums: 0,
+                total_claims_paid: 0,
+                pending_claims: 0,
+                rejections: RejectionStats::default(),
+                funding: FundingBreakdown::default(),
+            })
+    }
+
+    /// Get rejected claim counts per rejection reason
+    pub fn get_rejection_stats(env: Env) -> RejectionStats {
+        Self::get_stats(env.clone()).rejections
+    }
+
+    /// Add funds to the risk pool (admin only)
+    pub fn fund_risk_pool(env: Env, admin: Address, amount: i128) {
+        Self::fund_risk_pool_tagged(env.clone(), admin, amount, FundSource::Other);
+    }
+
+    /// Add funds to the risk pool, recording where they came from (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator adding the funds
+    /// * `amount` - Amount to add
+    /// * `source` - Origin of the funds
+    pub fn fund_risk_pool_tagged(env: Env, admin: Address, amount: i128, source: FundSource) {
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_admin(&env, admin.clone());
+
+        if amount <= 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let current_balance = Self::get_risk_pool_balance(env.clone());
+        let new_balance = current_balance + amount;
+        Self::set_risk_pool_balance(&env, new_balance);
+
+        let mut stats = Self::get_stats(env.clone());
+        stats.funding.record(source, amount);
+        Self::set_stats(&env, stats);
+
+        env.events().publish(
+            (Symbol::new(&env, "risk_pool_funded"), admin),
+            (amount, new_balance, source),
+        );
+    }
+
+    /// Write down the risk pool after a loss on its backing assets (admin only)
+    ///
+    /// Reserves are left untouched, so a loss can leave approved claims short of
+    /// funds; those fail, draw on the treasury or pay partially per the config.
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator recording the loss
+    /// * `amount` - Amount lost, at most the current risk pool balance
+    pub fn record_risk_pool_loss(env: Env, admin: Address, amount: i128) {
+        Self::require_admin(&env, admin.clone());
+
+        let current_balance = Self::get_risk_pool_balance(env.clone());
+        if amount <= 0 || amount > current_balance {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let new_balance = current_balance - amount;
+        Self::set_risk_pool_balance(&env, new_balance);
+
+        env.events().publish(
+            (Symbol::new(&env, "risk_pool_loss_recorded"), admin),
+            (amount, new_balance),
+        );
+    }
+
+    /// Get cumulative risk pool contributions per funding source
+    pub fn get_funding_breakdown(env: Env) -> FundingBreakdown {
+        Self::get_stats(env.clone()).funding
+    }
+
+    /// Quote the premium for a prospective policy
+    ///
+    /// The base annual rate is 0.1% of coverage per risk score point, prorated
+    /// by duration. When pool utilization including the new coverage exceeds
+    /// `utilization_threshold_bps`, `utilization_surcharge_bps` is added on top.
+    ///
+    /// # Arguments
+    /// * `coverage_amount` - Coverage amount requested
+    /// * `duration` - Policy duration in seconds
+    /// * `risk_score` - Risk score from backend analysis (0-100)
+    pub fn quote_premium(env: Env, coverage_amount: i128, duration: u64, risk_score: u32) -> i128 {
+        if coverage_amount <= 0 || duration == 0 || risk_score > 100 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let seconds_in_year: i128 = 365 * 24 * 60 * 60;
+        let base_premium = (coverage_amount * risk_score as i128 * 10 * duration as i128)
+            / (10000 * seconds_in_year);
+
+        let config = Self::get_config(env.clone());
+        let utilization_bps = Self::utilization_bps(&env, coverage_amount);
+        if utilization_bps > config.utilization_threshold_bps as i128 {
+            base_premium + (base_premium * config.utilization_surcharge_bps as i128) / 10000
+        } else {
+            base_premium
+        }
+    }
+
+    /// Get current risk pool balance
+    pub fn get_risk_pool_balance(env: Env) -> i128 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "risk_pool_balance"))
+            .unwrap_or(0)
+    }
+
+    /// Get the portion of the risk pool reserved for pending claims
+    pub fn get_reserved_balance(env: Env) -> i128 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "reserved_balance"))
+            .unwrap_or(0)
+    }
+
+    /// Get risk pool funds not reserved by pending claims
+    pub fn get_available_risk_pool_balance(env: Env) -> i128 {
+        Self::get_risk_pool_balance(env.clone()) - Self::get_reserved_balance(env.clone())
+    }
+
+    /// Get current premium pool balance
+    pub fn get_premium_pool_balance(env: Env) -> i128 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "premium_pool_balance"))
+            .unwrap_or(0)
+    }
+
+    /// Get the contract version and headline configuration
+    ///
+    /// Lets clients check compatibility with the deployed contract.
+    pub fn contract_info(env: Env) -> ContractInfo {
+        let config = Self::get_config(env.clone());
+
+        ContractInfo {
+            version: String::from_str(&env, CONTRACT_VERSION),
+            admin_count: Self::get_authorized_admins(env.clone()).len(),
+            max_coverage: config.max_coverage,
+            max_risk_score: config.max_risk_score,
+            claim_processing_approvals: config.claim_processing_approvals,
+            token_decimals: Self::get_token_decimals(env.clone()),
+            paused: SystemGuard::is_paused(&env),
+        }
+    }
+
+    /// Get contract configuration
+    pub fn get_config(env: Env) -> InsuranceConfig {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "config"))
+            .unwrap_or_else(|| InsuranceConfig::default(&env))
+    }
+
+    /// Get decimals of the premium/payout token
+    pub fn get_token_decimals(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "token_decimals"))
+            .unwrap_or(7)
+    }
+
+    /// Convert a whole-token amount to the token's base units
+    pub fn to_token_units(env: Env, amount: i128) -> i128 {
+        to_base_units(amount, Self::get_token_decimals(env.clone()))
+    }
+
+    /// Convert a base-unit amount to whole tokens
+    pub fn from_token_units(env: Env, amount: i128) -> i128 {
+        to_whole_units(amount, Self::get_token_decimals(env.clone()))
+    }
+
+    /// Update contract configuration (admin only)
+    pub fn update_config(env: Env, admin: Address, config: InsuranceConfig) {
+        Self::require_admin(&env, admin.clone());
+
+        if config.min_coverage > config.max_coverage
+            || config.min_duration > config.max_duration
+            || config.min_premium_bps > 10000
+            || config.oracle_tolerance_bps > 10000
+            || config.claim_processing_approvals == 0
+            || config.flagged_claim_approvals == 0
+            || config.premium_installments == 0
+            || config.absolute_payout_cap < 0
+            || config.bundle_discount_bps > 10000
+            || config.creation_risk_contribution_bps > 10000
+        {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "config"), &config);
+
+        env.events().publish((Symbol::new(&env, "config_updated"), admin), ());
+    }
+
+    /// Store a snapshot of the current configuration (admin only)
+    ///
+    /// Only the last `MAX_CONFIG_SNAPSHOTS` snapshots are kept. Returns the snapshot ID.
+    pub fn snapshot_config(env: Env, admin: Address) -> u32 {
+        Self::require_admin(&env, admin.clone());
+
+        let snapshot_id: u32 = env.storage().instance()
+            .get(&Symbol::new(&env, "next_config_snapshot"))
+            .unwrap_or(0);
+
+        let mut snapshots = Self::get_config_snapshots(&env);
+        snapshots.set(snapshot_id, Self::get_config(env.clone()));
+
+        // Evict the snapshot that fell out of the retention window
+        if snapshot_id >= MAX_CONFIG_SNAPSHOTS {
+            snapshots.remove(snapshot_id - MAX_CONFIG_SNAPSHOTS);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "config_snapshots"), &snapshots);
+        env.storage().instance().set(&Symbol::new(&env, "next_config_snapshot"), &(snapshot_id + 1));
+
+        env.events().publish(
+            (Symbol::new(&env, "config_snapshot_created"), admin),
+            snapshot_id,
+        );
+
+        snapshot_id
+    }
+
+    /// Restore the configuration from a retained snapshot (owner only)
+    ///
+    /// # Arguments
+    /// * `owner` - Contract owner performing the rollback
+    /// * `snapshot_id` - ID returned by `snapshot_config`
+    pub fn rollback_config(env: Env, owner: Address, snapshot_id: u32) {
+        Self::require_owner(&env, owner.clone());
+
+        let config = Self::get_config_snapshots(&env)
+            .get(snapshot_id)
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound));
+
+        env.storage().instance().set(&Symbol::new(&env, "config"), &config);
+
+        env.events().publish(
+            (Symbol::new(&env, "config_rolled_back"), owner),
+            snapshot_id,
+        );
+    }
+
+    /// Get the IDs of retained config snapshots, oldest first
+    pub fn get_config_snapshot_ids(env: Env) -> Vec<u32> {
+        Self::get_config_snapshots(&env).keys()
+    }
+
+    /// Pause all state-mutating operations (guardian only)
+    pub fn pause_system(env: Env, guardian: Address) {
+        SystemGuard::set_paused(&env, guardian.clone(), true);
+        env.events().publish((Symbol::new(&env, "system_paused"), guardian), ());
+    }
+
+    /// Resume state-mutating operations (guardian only)
+    pub fn unpause_system(env: Env, guardian: Address) {
+        SystemGuard::set_paused(&env, guardian.clone(), false);
+        env.events().publish((Symbol::new(&env, "system_unpaused"), guardian), ());
+    }
+
+    /// Check if the system is paused
+    pub fn is_system_paused(env: Env) -> bool {
+        SystemGuard::is_paused(&env)
+    }
+
+    /// Get the contract owner
+    pub fn get_owner(env: Env) -> Address {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "owner"))
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotInitialized))
+    }
+
+    /// Get authorized administrators
+    pub fn get_authorized_admins(env: Env) -> Vec<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "authorized_admins"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get yield pools policies may be associated with
+    pub fn get_registered_pools(env: Env) -> Vec<Bytes> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "registered_pools"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Register a yield pool (admin only)
+    pub fn register_pool(env: Env, admin: Address, pool_id: Bytes) {
+        Self::require_admin(&env, admin);
+
+        let mut pools = Self::get_registered_pools(env.clone());
+        if !pools.contains(&pool_id) {
+            pools.push_back(pool_id);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "registered_pools"), &pools);
+    }
+
+    /// Get the number of fraud-rejected claims associated with an address
+    pub fn get_fraud_flags(env: Env, address: Address) -> u32 {
+        Self::get_all_fraud_flags(&env).get(address).unwrap_or(0)
+    }
+
+    /// Get pending claims that have waited longer than the configured SLA, oldest first
+    ///
+    /// # Arguments
+    /// * `start` - Number of overdue claims to skip
+    /// * `limit` - Maximum number of claim IDs to return
+    pub fn get_overdue_claims(env: Env, start: u32, limit: u32) -> Vec<Bytes> {
+        let now = env.ledger().timestamp();
+        let claim_sla = Self::get_config(env.clone()).claim_sla;
+
+        let mut overdue = Vec::new(&env);
+        let mut skipped = 0;
+        for claim_id in Self::get_pending_claim_ids(&env).iter() {
+            if overdue.len() >= limit {
+                break;
+            }
+
+            // The queue is ordered by submission, so the first fresh claim ends the overdue run
+            let claim = Self::get_claim(env.clone(), claim_id.clone());
+            if now - claim.submitted_at <= claim_sla {
+                break;
+            }
+
+            if skipped < start {
+                skipped += 1;
+                continue;
+            }
+            overdue.push_back(claim_id);
+        }
+
+        overdue
+    }
+
+    /// Get the risk pool balance required before new coverage is backed
+    pub fn get_min_risk_pool(env: Env) -> i128 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "min_risk_pool"))
+            .unwrap_or(0)
+    }
+
+    /// Check whether the risk pool is funded enough to back new policies
+    pub fn is_solvent_to_write(env: Env) -> bool {
+        let available = Self::get_available_risk_pool_balance(env.clone());
+        available > 0 && available >= Self::get_min_risk_pool(env.clone())
+    }
+
+    /// Get the token premiums are collected in, if configured
+    pub fn get_premium_token(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "premium_token"))
+            .unwrap_or(None)
+    }
+
+    /// Get the token claims are paid in (defaults to the premium token)
+    pub fn get_payout_token(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "payout_token"))
+            .unwrap_or_else(|| Self::get_premium_token(env.clone()))
+    }
+
+    /// Get the rate source used to convert payouts, if any
+    pub fn get_rate_source(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "rate_source"))
+            .unwrap_or(None)
+    }
+
+    /// Configure settlement tokens and the conversion rate source (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator updating the settings
+    /// * `premium_token` - Token premiums, coverage and the risk pool are denominated in
+    /// * `payout_token` - Token claims are paid in
+    /// * `rate_source` - Rate source consulted when the two tokens differ
+    pub fn update_settlement_tokens(
+        env: Env,
+        admin: Address,
+        premium_token: Address,
+        payout_token: Address,
+        rate_source: Option<Address>,
+    ) {
+        Self::require_admin(&env, admin.clone());
+
+        // Distinct tokens can't be settled without a rate
+        if premium_token != payout_token && rate_source.is_none() {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "premium_token"), &Some(premium_token.clone()));
+        env.storage().instance().set(&Symbol::new(&env, "payout_token"), &Some(payout_token.clone()));
+        env.storage().instance().set(&Symbol::new(&env, "rate_source"), &rate_source);
+
+        env.events().publish(
+            (Symbol::new(&env, "settlement_tokens_updated"), admin),
+            (premium_token, payout_token, rate_source),
+        );
+    }
+
+    /// Convert a premium-token amount to the amount paid out in the payout token
+    ///
+    /// Returns `amount` unchanged when both tokens are the same.
+    pub fn quote_payout(env: Env, amount: i128) -> i128 {
+        let (premium_token, payout_token) = match (Self::get_premium_token(env.clone()), Self::get_payout_token(env.clone())) {
+            (Some(premium_token), Some(payout_token)) if premium_token != payout_token => (premium_token, payout_token),
+            _ => return amount,
+        };
+
+        let rate_source = Self::get_rate_source(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::InvalidState));
+        let rate = ConversionRateClient::new(&env, &rate_source).rate(&premium_token, &payout_token);
+        if rate <= 0 {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        amount.saturating_mul(rate) / RATE_SCALE
+    }
+
+    /// Get the treasury whose emergency fund backs the risk pool, if any
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "treasury"))
+            .unwrap_or(None)
+    }
+
+    /// Set or clear the treasury reference (admin only)
+    pub fn update_treasury(env: Env, admin: Address, treasury: Option<Address>) {
+        Self::require_admin(&env, admin);
+        env.storage().instance().set(&Symbol::new(&env, "treasury"), &treasury);
+    }
+
+    /// Get the yield aggregator reported on by `system_health`, if any
+    pub fn get_yield_aggregator(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "yield_aggregator"))
+            .unwrap_or(None)
+    }
+
+    /// Set or clear the yield aggregator reference (admin only)
+    pub fn update_yield_aggregator(env: Env, admin: Address, yield_aggregator: Option<Address>) {
+        Self::require_admin(&env, admin);
+        env.storage().instance().set(&Symbol::new(&env, "yield_aggregator"), &yield_aggregator);
+    }
+
+    /// Get combined health figures for the insurance, treasury and yield contracts
+    ///
+    /// Treasury and yield figures are `None` when the reference is unset or the
+    /// call fails, so one unhealthy contract doesn't hide the others.
+    pub fn system_health(env: Env) -> SystemHealth {
+        let risk_pool_balance = Self::get_risk_pool_balance(env.clone());
+        let reserved_balance = Self::get_reserved_balance(env.clone());
+
+        SystemHealth {
+            risk_pool_balance,
+            reserved_balance,
+            pending_claims: Self::get_stats(env.clone()).pending_claims,
+            solvent: risk_pool_balance >= reserved_balance
+                && risk_pool_balance - reserved_balance >= Self::get_min_risk_pool(env.clone()),
+            treasury_balance: Self::get_treasury(env.clone()).and_then(|treasury| Self::query_balance(&env, &treasury)),
+            yield_tvl: Self::get_yield_aggregator(env.clone()).and_then(|aggregator| Self::query_balance(&env, &aggregator)),
+        }
+    }
+
+    /// Get the price oracle used to verify market-loss claims, if any
+    pub fn get_price_oracle(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "price_oracle"))
+            .unwrap_or(None)
+    }
+
+    /// Set or clear the price oracle (admin only)
+    pub fn update_price_oracle(env: Env, admin: Address, oracle: Option<Address>) {
+        Self::require_admin(&env, admin.clone());
+        env.storage().instance().set(&Symbol::new(&env, "price_oracle"), &oracle);
+
+        env.events().publish(
+            (Symbol::new(&env, "price_oracle_updated"), admin),
+            oracle,
+        );
+    }
+
+    /// Deregister a deprecated yield pool (admin only)
+    pub fn deregister_pool(env: Env, admin: Address, pool_id: Bytes) {
+        Self::require_admin(&env, admin);
+
+        let mut pools = Self::get_registered_pools(env.clone());
+        let mut i = 0;
+        while i < pools.len() {
+            if pools.get(i).unwrap() == pool_id {
+                pools.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "registered_pools"), &pools);
+    }
+
+    /// Get authorized claim processors
     pub fn get_authorized_processors(env: Env) -> Vec<Address> {
         env.storage().instance()
             .get(&Symbol::new(&env, "authorized_processors"))
@@ -379,7 +1727,7 @@ impl InsuranceContract {
     pub fn add_authorized_processor(env: Env, admin: Address, processor: Address) {
         Self::require_admin(&env, admin);
 
-        let mut processors = Self::get_authorized_processors(&env);
+        let mut processors = Self::get_authorized_processors(env.clone());
         if !processors.contains(&processor) {
             processors.push_back(processor);
         }
@@ -391,11 +1739,13 @@ impl InsuranceContract {
     pub fn remove_authorized_processor(env: Env, admin: Address, processor: Address) {
         Self::require_admin(&env, admin);
 
-        let mut processors = Self::get_authorized_processors(&env);
+        let mut processors = Self::get_authorized_processors(env.clone());
+        AccessControl::require_above_minimum(&env, &processors, &processor);
+
         // Remove processor from the vector
         let mut i = 0;
         while i < processors.len() {
-            if processors.get(i).unwrap() == &processor {
+            if processors.get(i).unwrap() == processor {
                 processors.remove(i);
             } else {
                 i += 1;
@@ -407,20 +1757,111 @@ impl InsuranceContract {
 
     // Private helper methods
 
-    fn initialize(env: Env) {
+    fn initialize(env: &Env) {
         // Set initial empty data
-        env.storage().instance().set(&Symbol::new(&env, "policies"), &Map::new(&env));
-        env.storage().instance().set(&Symbol::new(&env, "user_policies"), &Map::new(&env));
-        env.storage().instance().set(&Symbol::new(&env, "claims"), &Map::new(&env));
-        env.storage().instance().set(&Symbol::new(&env, "risk_pool_balance"), &0);
-        env.storage().instance().set(&Symbol::new(&env, "premium_pool_balance"), &0);
+        env.storage().instance().set(&Symbol::new(env, "risk_pool_balance"), &0i128);
+        env.storage().instance().set(&Symbol::new(env, "reserved_balance"), &0i128);
+        env.storage().instance().set(&Symbol::new(env, "premium_pool_balance"), &0i128);
+        env.storage().instance().set(&Symbol::new(env, "config"), &InsuranceConfig::default(env));
+    }
+
+    fn require_owner(env: &Env, caller: Address) {
+        if caller != Self::get_owner(env.clone()) {
+            panic_with_error!(env, ContractError::Unauthorized);
+        }
+        caller.require_auth();
     }
 
     fn require_admin(env: &Env, caller: Address) {
-        let admins = Self::get_authorized_admins(env);
+        let admins = Self::get_authorized_admins(env.clone());
         if !admins.contains(&caller) {
             panic_with_error!(env, ContractError::Unauthorized);
         }
+        caller.require_auth();
+    }
+
+    fn query_balance(env: &Env, contract: &Address) -> Option<i128> {
+        match BalanceReportClient::new(env, contract).try_get_total_balance() {
+            Ok(Ok(balance)) => Some(balance),
+            _ => None,
+        }
+    }
+
+    fn require_pool_capacity(env: &Env, pool_id: &Bytes, additional_coverage: i128) {
+        let cap = Self::get_pool_coverage_cap(env.clone(), pool_id.clone());
+        if cap > 0 && Self::get_pool_exposure(env.clone(), pool_id.clone()) + additional_coverage > cap {
+            panic_with_error!(env, ContractError::InsufficientBalance);
+        }
+    }
+
+    fn adjust_pool_exposure(env: &Env, pool_id: &Bytes, delta: i128) {
+        let exposure = Self::get_pool_exposure(env.clone(), pool_id.clone()) + delta;
+        env.storage().instance().set(&(Symbol::new(env, "pool_exposure"), pool_id.clone()), &exposure.max(0));
+    }
+
+    /// Store a policy leaving the active set and release its exposure
+    fn close_policy(env: &Env, policy_id: &Bytes, policy: &Policy) {
+        env.storage().instance().set(&(Symbol::new(env, "policies"), policy_id.clone()), policy);
+        Self::adjust_pool_exposure(env, &policy.pool_id, -policy.coverage_amount);
+
+        let mut stats = Self::get_stats(env.clone());
+        stats.active_policies = stats.active_policies.saturating_sub(1);
+        Self::set_stats(env, stats);
+    }
+
+    fn get_config_snapshots(env: &Env) -> Map<u32, InsuranceConfig> {
+        env.storage().instance()
+            .get(&Symbol::new(env, "config_snapshots"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn get_all_fraud_flags(env: &Env) -> Map<Address, u32> {
+        env.storage().instance()
+            .get(&Symbol::new(env, "fraud_flags"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn draw_from_treasury(env: &Env, amount: i128) {
+        let treasury = Self::get_treasury(env.clone())
+            .unwrap_or_else(|| panic_with_error!(env, ContractError::InvalidState));
+
+        EmergencyFundClient::new(env, &treasury)
+            .draw_emergency_funds(&env.current_contract_address(), &amount);
+
+        let risk_pool_balance = Self::get_risk_pool_balance(env.clone());
+        Self::set_risk_pool_balance(env, risk_pool_balance + amount);
+
+        env.events().publish(
+            (Symbol::new(env, "emergency_draw"), treasury),
+            (amount, risk_pool_balance + amount),
+        );
+    }
+
+    /// Compare market-loss evidence against the price oracle.
+    ///
+    /// Returns true when the claimed value is outside the configured tolerance
+    /// and mismatches are only flagged; panics when they are set to reject.
+    fn verify_market_loss(env: &Env, evidence: &ClaimEvidence, config: &InsuranceConfig) -> bool {
+        if evidence.evidence_type != Symbol::new(env, "market_loss") {
+            return false;
+        }
+
+        let (oracle, claimed_value) = match (Self::get_price_oracle(env.clone()), evidence.market_value) {
+            (Some(oracle), Some(claimed_value)) => (oracle, claimed_value),
+            _ => return false,
+        };
+
+        let reported_value = PriceOracleClient::new(env, &oracle).market_value(&evidence.data);
+        let deviation = (claimed_value - reported_value).abs();
+        let tolerance = reported_value.abs().saturating_mul(config.oracle_tolerance_bps as i128) / 10000;
+        if deviation <= tolerance {
+            return false;
+        }
+
+        if config.reject_oracle_mismatch {
+            panic_with_error!(env, ContractError::InvalidClaimAmount);
+        }
+        true
     }
 
     fn validate_create_policy_params(env: &Env, params: &CreatePolicyParams) {
@@ -429,11 +1870,21 @@ impl InsuranceContract {
             panic!("Coverage amount must be positive");
         }
 
-        // Validate duration
-        if params.duration == 0 {
-            panic!("Duration must be positive");
+        // Coverage bounds are configured in whole tokens; compare in base units
+        let config = Self::get_config(env.clone());
+        if params.coverage_amount < Self::to_token_units(env.clone(), config.min_coverage)
+            || params.coverage_amount > Self::to_token_units(env.clone(), config.max_coverage)
+        {
+            panic_with_error!(env, ContractError::InvalidInput);
         }
 
+        // Validate duration; the expiry must also fit in a timestamp
+        if params.duration == 0 || env.ledger().timestamp().checked_add(params.duration).is_none() {
+            panic_with_error!(env, ContractError::InvalidInput);
+        }
+
+        Self::validate_duration(env, params.duration);
+
         // Validate risk score
         if params.risk_score > 100 {
             panic!("Risk score cannot exceed 100");
@@ -443,17 +1894,164 @@ impl InsuranceContract {
         if params.premium < 0 {
             panic!("Premium cannot be negative");
         }
+
+        if params.deductible < 0 || params.deductible > params.coverage_amount {
+            panic_with_error!(env, ContractError::InvalidInput);
+        }
+
+        // Reject underpriced policies
+        let min_premium = (params.coverage_amount * config.min_premium_bps as i128) / 10000;
+        if params.premium < min_premium {
+            panic_with_error!(env, ContractError::InvalidInput);
+        }
+    }
+
+    fn get_pending_claim_ids(env: &Env) -> Vec<Bytes> {
+        env.storage().instance()
+            .get(&Symbol::new(env, "pending_claim_ids"))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn total_pending_claim_amount(env: &Env) -> i128 {
+        let mut total = 0;
+        for claim_id in Self::get_pending_claim_ids(env).iter() {
+            total += Self::get_claim(env.clone(), claim_id.clone()).amount;
+        }
+        total
+    }
+
+    fn count_pending_claims(env: &Env, policy_id: &Bytes) -> u32 {
+        let mut count = 0;
+        for claim_id in Self::get_policy_claims(env.clone(), policy_id.clone()).iter() {
+            if Self::get_claim(env.clone(), claim_id.clone()).is_pending() {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    fn validate_duration(env: &Env, duration: u64) {
+        let config = Self::get_config(env.clone());
+        if duration < config.min_duration || duration > config.max_duration {
+            panic_with_error!(env, ContractError::InvalidInput);
+        }
+    }
+
+    fn utilization_bps(env: &Env, additional_coverage: i128) -> i128 {
+        let risk_pool_balance = Self::get_risk_pool_balance(env.clone());
+        let total_coverage = Self::get_stats(env.clone()).total_coverage + additional_coverage;
+
+        // An empty risk pool is treated as fully utilized
+        if risk_pool_balance <= 0 {
+            return i128::MAX;
+        }
+
+        total_coverage.saturating_mul(10000) / risk_pool_balance
     }
 
     fn set_stats(env: &Env, stats: PolicyStats) {
-        env.storage().instance().set(&Symbol::new(&env, "stats"), &stats);
+        env.storage().instance().set(&Symbol::new(env, "stats"), &stats);
     }
 
     fn set_risk_pool_balance(env: &Env, balance: i128) {
-        env.storage().instance().set(&Symbol::new(&env, "risk_pool_balance"), &balance);
+        env.storage().instance().set(&Symbol::new(env, "risk_pool_balance"), &balance);
+    }
+
+    fn set_reserved_balance(env: &Env, balance: i128) {
+        env.storage().instance().set(&Symbol::new(env, "reserved_balance"), &balance);
+    }
+
+    /// Net payout for a claim on `policy` against an effective `coverage`, clamped to the absolute payout cap if set
+    fn capped_payout(env: &Env, policy: &Policy, coverage: i128, claim_amount: i128) -> i128 {
+        let payout = policy.payout_against(coverage, claim_amount);
+        match Self::get_config(env.clone()).absolute_payout_cap {
+            0 => payout,
+            cap => payout.min(cap),
+        }
+    }
+
+    /// Record a payout already deducted from the risk pool
+    ///
+    /// Releases the claim's reserve (keeping any unpaid remainder reserved),
+    /// updates the claim, policy and statistics, and emits the payout events.
+    fn settle_claim(env: &Env, mut claim: Claim, payout: i128, unpaid_remainder: i128) {
+        let claim_id = claim.claim_id.clone();
+        let mut policy = Self::get_policy(env.clone(), claim.policy_id.clone());
+
+        Self::release_reserve(env, claim.amount - unpaid_remainder);
+
+        if unpaid_remainder > 0 {
+            claim.mark_as_partially_paid(env, unpaid_remainder);
+        } else {
+            claim.mark_as_paid(env);
+        }
+        env.storage().instance().set(&(Symbol::new(env, "claims"), claim_id.clone()), &claim);
+
+        // A claim exhausting what earlier payouts left of the coverage is a total loss and closes the policy
+        let closes_policy = claim.amount >= policy.remaining_of(claim.effective_coverage) && policy.status == PolicyStatus::Active;
+
+        policy.claimed_amount += payout;
+        env.storage().instance().set(&(Symbol::new(env, "policies"), claim.policy_id.clone()), &policy);
+        let mut premium_refund = 0;
+        if closes_policy {
+            if Self::get_config(env.clone()).refund_unused_premium {
+                // Never refund more than was paid or than the premium pool holds
+                premium_refund = policy.unused_premium(env)
+                    .min(policy.premium_paid_so_far)
+                    .min(Self::get_premium_pool_balance(env.clone()));
+                if premium_refund > 0 {
+                    let premium_pool_balance = Self::get_premium_pool_balance(env.clone());
+                    Self::set_premium_pool_balance(env, premium_pool_balance - premium_refund);
+                }
+            }
+
+            policy.status = PolicyStatus::Claimed;
+            env.storage().instance().set(&(Symbol::new(env, "policies"), claim.policy_id.clone()), &policy);
+            Self::adjust_pool_exposure(env, &policy.pool_id, -policy.coverage_amount);
+        }
+
+        // Update statistics
+        let mut stats = Self::get_stats(env.clone());
+        stats.total_claims_paid += payout;
+        if closes_policy {
+            stats.active_policies = stats.active_policies.saturating_sub(1);
+        }
+        Self::set_stats(env, stats);
+
+        // Refund the unused premium to the holder who paid it
+        // In production, this would transfer funds to the holder
+        if premium_refund > 0 {
+            env.events().publish(
+                (Symbol::new(env, "premium_refunded"), claim.policy_id.clone()),
+                (policy.holder.clone(), premium_refund),
+            );
+        }
+
+        // Pay the beneficiary if one is set, otherwise the claimant, in the payout token
+        // In production, this would transfer funds to the payee
+        env.events().publish(
+            (Symbol::new(env, "claim_paid"), claim_id),
+            (
+                policy.payout_address(),
+                Self::quote_payout(env.clone(), payout),
+                claim.reference,
+            ),
+        );
+
+        if unpaid_remainder > 0 {
+            env.events().publish(
+                (Symbol::new(env, "claim_partially_paid"), claim.claim_id),
+                (payout, unpaid_remainder),
+            );
+        }
+    }
+
+    fn release_reserve(env: &Env, amount: i128) {
+        let reserved_balance = Self::get_reserved_balance(env.clone());
+        Self::set_reserved_balance(env, (reserved_balance - amount).max(0));
     }
 
     fn set_premium_pool_balance(env: &Env, balance: i128) {
-        env.storage().instance().set(&Symbol::new(&env, "premium_pool_balance"), &balance);
+        env.storage().instance().set(&Symbol::new(env, "premium_pool_balance"), &balance);
     }
 }
\ No newline at end of file
@@ -93,7 +93,7 @@ impl InsuranceContract {
 
         // Check if policy exists and is active
         let policy = contract.policies.get(policy_id.clone())
-            .unwrap_or_else(|| panic!("Policy not found"));
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::PolicyNotFound));
 
         if !policy.active {
             panic_with_error!(&env, ContractError::InvalidState);
@@ -138,7 +138,7 @@ impl InsuranceContract {
 
         // Get and update claim
         let mut claim = contract.claims.get(claim_id.clone())
-            .unwrap_or_else(|| panic!("Claim not found"));
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound));
 
         if claim.processed {
             panic_with_error!(&env, ContractError::InvalidState);
@@ -150,7 +150,7 @@ impl InsuranceContract {
         // If approved, deactivate the policy
         if approve {
             let mut policy = contract.policies.get(claim.policy_id.clone())
-                .unwrap_or_else(|| panic!("Policy not found"));
+                .unwrap_or_else(|| panic_with_error!(&env, ContractError::PolicyNotFound));
             policy.active = false;
             contract.policies.set(claim.policy_id, policy);
         }
@@ -169,7 +169,7 @@ impl InsuranceContract {
             .unwrap_or_else(|| panic!("Contract not initialized"));
 
         contract.policies.get(policy_id)
-            .unwrap_or_else(|| panic!("Policy not found"))
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::PolicyNotFound))
     }
 
     /// Get claim information
@@ -179,7 +179,7 @@ impl InsuranceContract {
             .unwrap_or_else(|| panic!("Contract not initialized"));
 
         contract.claims.get(claim_id)
-            .unwrap_or_else(|| panic!("Claim not found"))
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound))
     }
 
     /// Get all policies for a user
@@ -1,4 +1,7 @@
 //! Insurance contract for policy management, premium collection, and claim processing
 
-pub mod contract_simple;
-pub use contract_simple::{InsuranceContract, Policy, Claim};
\ No newline at end of file
+pub mod contract;
+pub mod types;
+
+pub use contract::{InsuranceConfig, InsuranceContract, InsuranceContractClient};
+pub use types::{Claim, ClaimEvidence, ContractInfo, CreatePolicyParams, FundSource, FundingBreakdown, Policy, PolicyReceipt, PolicyStats, RejectionReason, RejectionStats, SystemHealth};
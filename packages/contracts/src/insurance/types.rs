@@ -1,11 +1,12 @@
 //! Insurance contract types
 
-use soroban_sdk::{Address, Bytes, Env, Map, Symbol, Vec};
+use soroban_sdk::{contractclient, contracttype, Address, Bytes, Env, Map, String, Symbol, Vec};
 
-use crate::shared::{PolicyStatus, ClaimStatus};
+use crate::shared::{saturating_u32, PolicyStatus, ClaimStatus};
 
 /// Policy structure representing an insurance policy
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct Policy {
     /// Policy holder's address
     pub holder: Address,
@@ -25,10 +26,47 @@ pub struct Policy {
     pub pool_id: Bytes,
     /// Additional metadata
     pub metadata: Map<Symbol, Bytes>,
+    /// Optional payout recipient (defaults to the holder)
+    pub beneficiary: Option<Address>,
+    /// Number of claims awaiting processing
+    pub pending_claims: u32,
+    /// Timestamp of the most recent claim submitted against the policy
+    pub last_claim_at: Option<u64>,
+    /// Amount deducted from each payout
+    pub deductible: i128,
+    /// Total paid out on the policy so far
+    pub claimed_amount: i128,
+    /// Premium received for the current term so far
+    pub premium_paid_so_far: i128,
+    /// Number of equal installments the premium is paid in over the term
+    pub installments: u32,
+    /// Whether coverage declines linearly to zero over the term
+    pub declining: bool,
+}
+
+/// Deployed version and headline configuration of the insurance contract
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ContractInfo {
+    /// Semantic version of the contract
+    pub version: String,
+    /// Number of authorized administrators
+    pub admin_count: u32,
+    /// Maximum coverage amount, in whole tokens
+    pub max_coverage: i128,
+    /// Maximum accepted risk score
+    pub max_risk_score: u32,
+    /// Approvals required to process a claim
+    pub claim_processing_approvals: u32,
+    /// Decimals of the premium/payout token
+    pub token_decimals: u32,
+    /// Whether the system is paused
+    pub paused: bool,
 }
 
 /// Claim structure for insurance claims
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct Claim {
     /// Unique claim identifier
     pub claim_id: Bytes,
@@ -40,8 +78,8 @@ pub struct Claim {
     pub amount: i128,
     /// Current status of the claim
     pub status: ClaimStatus,
-    /// Evidence supporting the claim
-    pub evidence: ClaimEvidence,
+    /// Evidence items supporting the claim
+    pub evidence: Vec<ClaimEvidence>,
     /// Timestamp when claim was submitted
     pub submitted_at: u64,
     /// Timestamp when claim was processed
@@ -50,10 +88,87 @@ pub struct Claim {
     pub processor: Option<Address>,
     /// Reason for claim approval/rejection
     pub reason: Symbol,
+    /// Categorized rejection reason, only meaningful once the claim is rejected
+    pub rejection_reason: RejectionReason,
+    /// Earliest time an approved claim can be paid out
+    pub payable_after: Option<u64>,
+    /// Off-chain reference (e.g. support ticket) included in claim events
+    pub reference: Option<Symbol>,
+    /// Set when the price oracle disagreed with the claimed market value
+    pub oracle_flagged: bool,
+    /// Number of processor approvals needed before the claim is approved
+    pub required_approvals: u32,
+    /// Processors who have approved the claim so far
+    pub approvals: Vec<Address>,
+    /// Approved amount still owed after a partial payout
+    pub unpaid_remainder: i128,
+    /// Policy's effective coverage when the claim was submitted, capping its payout
+    pub effective_coverage: i128,
+}
+
+/// Categorized reason for rejecting a claim
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum RejectionReason {
+    /// Evidence does not support the claim
+    InsufficientEvidence,
+    /// Loss is not covered by the policy
+    OutOfCoverage,
+    /// Claim is suspected to be fraudulent
+    FraudSuspected,
+    /// Policy was not active
+    PolicyInactive,
+    /// Any other reason
+    Other,
+}
+
+/// Number of rejected claims per rejection reason
+#[derive(Clone, Debug, Default)]
+#[contracttype]
+pub struct RejectionStats {
+    /// Rejections for insufficient evidence
+    pub insufficient_evidence: u64,
+    /// Rejections for losses outside coverage
+    pub out_of_coverage: u64,
+    /// Rejections for suspected fraud
+    pub fraud_suspected: u64,
+    /// Rejections for inactive policies
+    pub policy_inactive: u64,
+    /// Rejections for other reasons
+    pub other: u64,
+}
+
+/// Origin of capital added to the risk pool
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum FundSource {
+    /// Premiums collected from policy holders
+    Premiums,
+    /// Yield routed from the yield aggregator
+    Yield,
+    /// External grants or donations
+    Grant,
+    /// Direct admin deposits and any other source
+    Other,
+}
+
+/// Cumulative risk pool contributions per funding source
+#[derive(Clone, Debug, Default)]
+#[contracttype]
+pub struct FundingBreakdown {
+    /// Contributions from premiums
+    pub premiums: i128,
+    /// Contributions from yield
+    pub yield_income: i128,
+    /// Contributions from grants
+    pub grants: i128,
+    /// Contributions from other sources
+    pub other: i128,
 }
 
 /// Evidence supporting an insurance claim
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct ClaimEvidence {
     /// Type of evidence
     pub evidence_type: Symbol,
@@ -63,10 +178,99 @@ pub struct ClaimEvidence {
     pub timestamp: u64,
     /// Additional notes
     pub notes: Symbol,
+    /// Market value claimed by the holder, for market-loss evidence
+    pub market_value: Option<i128>,
+}
+
+/// Treasury emergency fund the insurance contract can draw on
+#[contractclient(name = "EmergencyFundClient")]
+pub trait EmergencyFund {
+    /// Draw `amount` from the emergency fund on behalf of `caller`
+    fn draw_emergency_funds(env: Env, caller: Address, amount: i128);
+}
+
+/// Balance reporting exposed by the treasury and yield aggregator
+#[contractclient(name = "BalanceReportClient")]
+pub trait BalanceReport {
+    /// Total balance held by the contract
+    fn get_total_balance(env: Env) -> i128;
+}
+
+/// Combined health of the insurance, treasury and yield aggregator contracts
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct SystemHealth {
+    /// Risk pool balance
+    pub risk_pool_balance: i128,
+    /// Risk pool funds reserved for pending claims
+    pub reserved_balance: i128,
+    /// Number of claims awaiting processing
+    pub pending_claims: u64,
+    /// Whether the risk pool covers its reservations and minimum funding
+    pub solvent: bool,
+    /// Treasury total balance (`None` if unset or unreachable)
+    pub treasury_balance: Option<i128>,
+    /// Yield aggregator total value locked (`None` if unset or unreachable)
+    pub yield_tvl: Option<i128>,
+}
+
+/// Fixed-point scale of rates returned by a `ConversionRate` source (1.0 = 10^7)
+pub const RATE_SCALE: i128 = 10_000_000;
+
+/// Exchange-rate source used to convert payouts between tokens
+#[contractclient(name = "ConversionRateClient")]
+pub trait ConversionRate {
+    /// Units of `to` per unit of `from`, scaled by `RATE_SCALE`
+    fn rate(env: Env, from: Address, to: Address) -> i128;
+}
+
+/// Price oracle consulted to verify market-loss claims
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    /// Report the current market value of the position described by `pool_data`
+    fn market_value(env: Env, pool_data: Bytes) -> i128;
+}
+
+/// Outcome of a batch claim payout
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BatchPayoutSummary {
+    /// Claims paid in full
+    pub paid: Vec<Bytes>,
+    /// Claims that were not payable or didn't fit in the risk pool
+    pub skipped: Vec<Bytes>,
+    /// Total deducted from the risk pool
+    pub total_paid: i128,
+}
+
+impl BatchPayoutSummary {
+    /// Create an empty summary
+    pub fn new(env: &Env) -> Self {
+        Self {
+            paid: Vec::new(env),
+            skipped: Vec::new(env),
+            total_paid: 0,
+        }
+    }
+}
+
+/// Snapshot of a newly created policy returned by `create_policy`
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct PolicyReceipt {
+    /// ID the policy was stored under
+    pub policy_id: Bytes,
+    /// Premium charged for the policy
+    pub premium: i128,
+    /// Timestamp when coverage ends
+    pub expiry_time: u64,
+    /// Coverage after the risk-score reduction
+    pub effective_coverage: i128,
 }
 
 /// Policy creation parameters
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct CreatePolicyParams {
     /// Policy holder
     pub holder: Address,
@@ -80,10 +284,17 @@ pub struct CreatePolicyParams {
     pub pool_id: Bytes,
     /// Premium amount calculated by backend
     pub premium: i128,
+    /// Optional payout recipient (defaults to the holder)
+    pub beneficiary: Option<Address>,
+    /// Amount deducted from each payout
+    pub deductible: i128,
+    /// Whether coverage declines linearly to zero over the term
+    pub declining: bool,
 }
 
 /// Policy statistics
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct PolicyStats {
     /// Total number of active policies
     pub active_policies: u64,
@@ -95,11 +306,15 @@ pub struct PolicyStats {
     pub total_claims_paid: i128,
     /// Number of pending claims
     pub pending_claims: u64,
+    /// Rejected claim counts per reason
+    pub rejections: RejectionStats,
+    /// Risk pool contributions per funding source
+    pub funding: FundingBreakdown,
 }
 
 impl Policy {
     /// Create a new policy
-    pub fn new(params: CreatePolicyParams, policy_id: Bytes, env: &Env) -> Self {
+    pub fn new(params: CreatePolicyParams, _policy_id: Bytes, env: &Env) -> Self {
         let current_time = env.ledger().timestamp();
 
         Self {
@@ -112,12 +327,93 @@ impl Policy {
             status: PolicyStatus::Active,
             pool_id: params.pool_id,
             metadata: Map::new(env),
+            beneficiary: params.beneficiary,
+            pending_claims: 0,
+            last_claim_at: None,
+            deductible: params.deductible,
+            claimed_amount: 0,
+            premium_paid_so_far: 0,
+            installments: 1,
+            declining: params.declining,
         }
     }
 
+    /// Check if the full premium for the current term has been paid
+    pub fn is_premium_paid(&self) -> bool {
+        self.premium_paid_so_far >= self.premium
+    }
+
+    /// Get the premium that must have been paid by now under the installment schedule
+    ///
+    /// The term is split into `installments` equal periods and each installment is
+    /// due at the start of its period; the last one absorbs any rounding.
+    pub fn premium_due(&self, env: &Env) -> i128 {
+        let installments = self.installments.max(1);
+        let period = (self.duration / installments as u64).max(1);
+        let elapsed = env.ledger().timestamp().saturating_sub(self.start_time);
+        let due_installments = (elapsed / period + 1).min(installments as u64);
+
+        if due_installments == installments as u64 {
+            self.premium
+        } else {
+            self.premium * due_installments as i128 / installments as i128
+        }
+    }
+
+    /// Check if premium payments are up to date with the installment schedule
+    pub fn is_paid_up(&self, env: &Env) -> bool {
+        self.premium_paid_so_far >= self.premium_due(env)
+    }
+
+    /// Get the coverage not yet used by earlier payouts
+    pub fn remaining_coverage(&self, env: &Env) -> i128 {
+        self.remaining_of(self.effective_coverage(env))
+    }
+
+    /// Get what earlier payouts left of a given effective `coverage`
+    pub fn remaining_of(&self, coverage: i128) -> i128 {
+        (coverage - self.claimed_amount).max(0)
+    }
+
+    /// Get the net payout for a claim of `claim_amount` against an effective `coverage`
+    ///
+    /// The claim is capped at what earlier payouts left of the coverage, then the
+    /// deductible is subtracted; the result never goes below zero.
+    pub fn payout_against(&self, coverage: i128, claim_amount: i128) -> i128 {
+        (claim_amount.min(self.remaining_of(coverage)) - self.deductible).max(0)
+    }
+
+    /// Check if a new claim is still blocked by the cooldown since the last one
+    pub fn is_in_claim_cooldown(&self, env: &Env, cooldown: u64) -> bool {
+        match self.last_claim_at {
+            Some(last_claim_at) => env.ledger().timestamp() < last_claim_at + cooldown,
+            None => false,
+        }
+    }
+
+    /// Check if claims are still barred by the waiting period after purchase
+    pub fn is_in_waiting_period(&self, env: &Env, waiting_period: u64) -> bool {
+        env.ledger().timestamp() < self.start_time + waiting_period
+    }
+
+    /// Get the address claim payouts are sent to
+    pub fn payout_address(&self) -> Address {
+        self.beneficiary.clone().unwrap_or_else(|| self.holder.clone())
+    }
+
+    /// Check if the policy has claims awaiting processing
+    pub fn has_pending_claims(&self) -> bool {
+        self.pending_claims > 0
+    }
+
     /// Get the expiry time of the policy
+    ///
+    /// Panics if `start_time + duration` overflows; durations are bounded at
+    /// creation so this only triggers on corrupted state.
     pub fn expiry_time(&self) -> u64 {
-        self.start_time + self.duration
+        self.start_time
+            .checked_add(self.duration)
+            .unwrap_or_else(|| panic!("Policy expiry time overflows"))
     }
 
     /// Check if the policy is currently active
@@ -131,10 +427,29 @@ impl Policy {
     }
 
     /// Calculate remaining coverage based on risk score
-    pub fn effective_coverage(&self) -> i128 {
+    ///
+    /// Declining policies are further scaled by the fraction of the term remaining.
+    pub fn effective_coverage(&self, env: &Env) -> i128 {
         // Effective coverage is reduced by risk percentage
         let risk_reduction = (self.coverage_amount * self.risk_score as i128) / 100;
-        self.coverage_amount - risk_reduction
+        let coverage = self.coverage_amount - risk_reduction;
+
+        if !self.declining || self.duration == 0 {
+            return coverage;
+        }
+
+        let remaining = self.expiry_time().saturating_sub(env.ledger().timestamp());
+        (coverage * remaining as i128) / self.duration as i128
+    }
+
+    /// Get the premium for the unused remainder of the term, pro-rated by time
+    pub fn unused_premium(&self, env: &Env) -> i128 {
+        if self.duration == 0 {
+            return 0;
+        }
+
+        let remaining = self.expiry_time().saturating_sub(env.ledger().timestamp());
+        (self.premium * remaining as i128) / self.duration as i128
     }
 
     /// Get the premium as a percentage of coverage
@@ -142,7 +457,7 @@ impl Policy {
         if self.coverage_amount == 0 {
             return 0;
         }
-        ((self.premium * 10000) / self.coverage_amount) as u32
+        saturating_u32(self.premium.saturating_mul(10000) / self.coverage_amount)
     }
 }
 
@@ -154,6 +469,7 @@ impl Claim {
         claimant: Address,
         amount: i128,
         evidence: ClaimEvidence,
+        reference: Option<Symbol>,
         env: &Env,
     ) -> Self {
         Self {
@@ -162,35 +478,63 @@ impl Claim {
             claimant,
             amount,
             status: ClaimStatus::Pending,
-            evidence,
+            evidence: Vec::from_array(env, [evidence]),
             submitted_at: env.ledger().timestamp(),
             processed_at: None,
             processor: None,
-            reason: Symbol::new(&env, "pending"),
+            reason: Symbol::new(env, "pending"),
+            rejection_reason: RejectionReason::Other,
+            payable_after: None,
+            reference,
+            oracle_flagged: false,
+            required_approvals: 1,
+            approvals: Vec::new(env),
+            unpaid_remainder: 0,
+            // A claim never exceeds coverage; submit_claim records the exact figure
+            effective_coverage: amount,
+        }
+    }
+
+    /// Record a processor's approval, returning true once enough approvals are in
+    pub fn add_approval(&mut self, processor: Address) -> bool {
+        if !self.approvals.contains(&processor) {
+            self.approvals.push_back(processor);
         }
+        self.approvals.len() >= self.required_approvals
     }
 
-    /// Approve the claim
-    pub fn approve(&mut self, processor: Address, reason: Symbol, env: &Env) {
+    /// Approve the claim, making it payable once `settlement_delay` has passed
+    pub fn approve(&mut self, processor: Address, reason: Symbol, settlement_delay: u64, env: &Env) {
         self.status = ClaimStatus::Approved;
         self.processed_at = Some(env.ledger().timestamp());
         self.processor = Some(processor);
         self.reason = reason;
+        self.payable_after = Some(env.ledger().timestamp() + settlement_delay);
     }
 
     /// Reject the claim
-    pub fn reject(&mut self, processor: Address, reason: Symbol, env: &Env) {
+    pub fn reject(&mut self, processor: Address, reason: Symbol, rejection_reason: RejectionReason, env: &Env) {
         self.status = ClaimStatus::Rejected;
         self.processed_at = Some(env.ledger().timestamp());
         self.processor = Some(processor);
         self.reason = reason;
+        self.rejection_reason = rejection_reason;
     }
 
     /// Mark claim as paid
     pub fn mark_as_paid(&mut self, env: &Env) {
         self.status = ClaimStatus::Paid;
         self.processed_at = Some(env.ledger().timestamp());
-        self.reason = Symbol::new(&env, "paid");
+        self.reason = Symbol::new(env, "paid");
+        self.unpaid_remainder = 0;
+    }
+
+    /// Mark the claim as paid in part, recording the amount still owed
+    pub fn mark_as_partially_paid(&mut self, env: &Env, unpaid_remainder: i128) {
+        self.status = ClaimStatus::PartiallyPaid;
+        self.processed_at = Some(env.ledger().timestamp());
+        self.reason = Symbol::new(env, "partially_paid");
+        self.unpaid_remainder = unpaid_remainder;
     }
 
     /// Check if the claim is pending
@@ -198,9 +542,47 @@ impl Claim {
         self.status == ClaimStatus::Pending
     }
 
+    /// Check if the claim is approved and past its settlement delay
+    pub fn is_payable(&self, env: &Env) -> bool {
+        match (self.status, self.payable_after) {
+            (ClaimStatus::Approved, Some(payable_after)) => env.ledger().timestamp() >= payable_after,
+            _ => false,
+        }
+    }
+
     /// Check if the claim has been processed
     pub fn is_processed(&self) -> bool {
-        matches!(self.status, ClaimStatus::Approved | ClaimStatus::Rejected | ClaimStatus::Paid)
+        matches!(self.status, ClaimStatus::Approved | ClaimStatus::Rejected | ClaimStatus::Paid | ClaimStatus::PartiallyPaid)
+    }
+}
+
+impl RejectionStats {
+    /// Count a rejection for the given reason
+    pub fn record(&mut self, reason: RejectionReason) {
+        match reason {
+            RejectionReason::InsufficientEvidence => self.insufficient_evidence += 1,
+            RejectionReason::OutOfCoverage => self.out_of_coverage += 1,
+            RejectionReason::FraudSuspected => self.fraud_suspected += 1,
+            RejectionReason::PolicyInactive => self.policy_inactive += 1,
+            RejectionReason::Other => self.other += 1,
+        }
+    }
+}
+
+impl FundingBreakdown {
+    /// Add a contribution from the given source
+    pub fn record(&mut self, source: FundSource, amount: i128) {
+        match source {
+            FundSource::Premiums => self.premiums += amount,
+            FundSource::Yield => self.yield_income += amount,
+            FundSource::Grant => self.grants += amount,
+            FundSource::Other => self.other += amount,
+        }
+    }
+
+    /// Get the total contributed across all sources
+    pub fn total(&self) -> i128 {
+        self.premiums + self.yield_income + self.grants + self.other
     }
 }
 
@@ -212,26 +594,29 @@ impl ClaimEvidence {
             data,
             timestamp: env.ledger().timestamp(),
             notes,
+            market_value: None,
         }
     }
 
     /// Create evidence for market loss
     pub fn market_loss_evidence(pool_data: Bytes, market_value: i128, env: &Env) -> Self {
         Self {
-            evidence_type: Symbol::new(&env, "market_loss"),
+            evidence_type: Symbol::new(env, "market_loss"),
             data: pool_data,
             timestamp: env.ledger().timestamp(),
-            notes: Symbol::new(&env, "market_value_loss"),
+            notes: Symbol::new(env, "market_value_loss"),
+            market_value: Some(market_value),
         }
     }
 
     /// Create evidence for smart contract failure
     pub fn contract_failure_evidence(contract_data: Bytes, error_details: Symbol, env: &Env) -> Self {
         Self {
-            evidence_type: Symbol::new(&env, "contract_failure"),
+            evidence_type: Symbol::new(env, "contract_failure"),
             data: contract_data,
             timestamp: env.ledger().timestamp(),
             notes: error_details,
+            market_value: None,
         }
     }
 }
\ No newline at end of file
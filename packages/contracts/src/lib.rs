@@ -19,10 +19,14 @@ pub mod simple_insurance;
 pub mod hello;
 pub mod yield_aggregator_simple;
 pub mod treasury_simple;
-// Disable problematic contracts for now
-// pub mod yield_aggregator;
-// pub mod treasury;
-// pub mod insurance;
+// The full contracts share entry point names with the simple ones, so they
+// stay out of the single WASM artifact until each gets its own crate
+#[cfg(not(target_family = "wasm"))]
+pub mod yield_aggregator;
+#[cfg(not(target_family = "wasm"))]
+pub mod treasury;
+#[cfg(not(target_family = "wasm"))]
+pub mod insurance;
 
 // Export working contracts
 pub use simple_insurance::SimpleInsurance;
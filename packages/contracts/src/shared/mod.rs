@@ -1,6 +1,6 @@
 //! Shared types and utilities used across all contracts
 
-use soroban_sdk::{Address, Env, Error, Vec, panic_with_error};
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Error, Map, String, Symbol, Vec, panic_with_error};
 
 /// Role-based access control system
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -44,6 +44,16 @@ pub enum ContractError {
     InsufficientApprovals = 13,
     /// Risk score out of range
     RiskScoreOutOfRange = 14,
+    /// Requested record does not exist
+    NotFound = 15,
+    /// System has been paused by the guardian
+    SystemPaused = 16,
+    /// Contract state was read before the contract was initialized
+    NotInitialized = 17,
+    /// Emergency transfers would exceed the amount allowed in the current window
+    EmergencyLimitExceeded = 18,
+    /// Claim filed before the policy's waiting period has elapsed
+    WaitingPeriodActive = 19,
 }
 
 impl From<ContractError> for Error {
@@ -52,8 +62,81 @@ impl From<ContractError> for Error {
     }
 }
 
+/// Convert an `i128` calculation result to `u32`, saturating instead of wrapping
+///
+/// Negative values clamp to `0` and values above `u32::MAX` clamp to `u32::MAX`.
+pub fn saturating_u32(value: i128) -> u32 {
+    value.clamp(0, u32::MAX as i128) as u32
+}
+
+/// Scale a whole-token amount to base units for a token with `decimals` decimals
+///
+/// Saturates at `i128::MAX`/`i128::MIN` instead of overflowing.
+pub fn to_base_units(amount: i128, decimals: u32) -> i128 {
+    amount.saturating_mul(10i128.saturating_pow(decimals))
+}
+
+/// Convert a base-unit amount to whole tokens, truncating any fractional part
+pub fn to_whole_units(amount: i128, decimals: u32) -> i128 {
+    amount / 10i128.saturating_pow(decimals)
+}
+
+/// Semantic version of the deployed contracts, reported by `contract_info`
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Account strkey with an all-zero public key, commonly used as a burn address
+pub const ZERO_ACCOUNT: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+/// Contract strkey with an all-zero contract ID
+pub const ZERO_CONTRACT: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+
+/// Check whether `address` is the zero account or zero contract address
+pub fn is_zero_address(env: &Env, address: &Address) -> bool {
+    *address == Address::from_string(&String::from_str(env, ZERO_ACCOUNT))
+        || *address == Address::from_string(&String::from_str(env, ZERO_CONTRACT))
+}
+
+/// Panic with `ContractError::InvalidInput` if `address` is a zero address
+///
+/// Funds sent to a zero address can never be recovered.
+pub fn require_nonzero_address(env: &Env, address: &Address) {
+    if is_zero_address(env, address) {
+        panic_with_error!(env, ContractError::InvalidInput);
+    }
+}
+
+/// Split `amount` by a whole-number percentage
+///
+/// Returns `(share, remainder)`. The share truncates toward zero and the
+/// remainder absorbs the rounding, so `share + remainder == amount` always.
+pub fn split_by_percentage(amount: i128, percentage: u32) -> (i128, i128) {
+    let share = amount.saturating_mul(percentage as i128) / 100;
+    (share, amount - share)
+}
+
+/// Derive a collision-resistant ID from a prefix, caller, nonce and the ledger sequence
+///
+/// The ID is the SHA-256 of the XDR-encoded prefix and caller followed by the
+/// big-endian nonce and sequence, so the same inputs always produce the same ID.
+pub fn generate_id(env: &Env, prefix: Symbol, caller: &Address, nonce: u64) -> Bytes {
+    let mut preimage = prefix.to_xdr(env);
+    preimage.append(&caller.clone().to_xdr(env));
+    preimage.extend_from_array(&nonce.to_be_bytes());
+    preimage.extend_from_array(&env.ledger().sequence().to_be_bytes());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Return the contract's next ID nonce and advance the counter
+pub fn next_id_nonce(env: &Env) -> u64 {
+    let key = Symbol::new(env, "id_nonce");
+    let nonce: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    env.storage().instance().set(&key, &(nonce + 1));
+    nonce
+}
+
 /// Policy status
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
 pub enum PolicyStatus {
     /// Policy is active
     Active,
@@ -67,6 +150,7 @@ pub enum PolicyStatus {
 
 /// Claim status
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
 pub enum ClaimStatus {
     /// Claim is pending approval
     Pending,
@@ -76,11 +160,19 @@ pub enum ClaimStatus {
     Rejected,
     /// Claim has been paid out
     Paid,
+    /// Claim was paid in part; the rest is owed once the pool is refunded
+    PartiallyPaid,
 }
 
 /// Access control trait for role-based permissions
 pub struct AccessControl;
 
+/// Minimum number of addresses that must keep a management role
+///
+/// Removals that would leave fewer holders are rejected so a contract can't
+/// lock itself out of administration.
+pub const MIN_ADMINS: u32 = 1;
+
 impl AccessControl {
     /// Require a specific role to execute a function
     pub fn require_role(env: &Env, authorized_roles: Vec<Address>, caller: Address, required_role: Role) {
@@ -105,8 +197,18 @@ impl AccessControl {
         }
     }
 
+    /// Panic with `ContractError::InvalidState` if removing `user` would leave
+    /// fewer than `MIN_ADMINS` role holders
+    pub fn require_above_minimum(env: &Env, authorized_roles: &Vec<Address>, user: &Address) {
+        if authorized_roles.contains(user) && authorized_roles.len() <= MIN_ADMINS {
+            panic_with_error!(env, ContractError::InvalidState);
+        }
+    }
+
     /// Revoke a role from an address (admin only)
-    pub fn revoke_role(_env: &Env, authorized_roles: &mut Vec<Address>, _admin: Address, user: Address) {
+    pub fn revoke_role(env: &Env, authorized_roles: &mut Vec<Address>, _admin: Address, user: Address) {
+        Self::require_above_minimum(env, authorized_roles, &user);
+
         // Remove user from authorized roles
         let mut i = 0;
         while i < authorized_roles.len() {
@@ -119,6 +221,112 @@ impl AccessControl {
     }
 }
 
+/// Initialization guard to prevent a contract from being constructed twice
+pub struct InitGuard;
+
+impl InitGuard {
+    /// Check if the contract has already been initialized
+    pub fn is_initialized(env: &Env) -> bool {
+        env.storage().instance().has(&Symbol::new(env, "initialized"))
+    }
+
+    /// Panic if the contract has already been initialized, otherwise mark it as initialized
+    pub fn initialize_once(env: &Env) {
+        if Self::is_initialized(env) {
+            panic_with_error!(env, ContractError::InvalidState);
+        }
+        env.storage().instance().set(&Symbol::new(env, "initialized"), &true);
+    }
+}
+
+/// System-wide pause switch controlled by a guardian address
+///
+/// Each contract stores the guardian at deploy time; the guardian can pause
+/// every contract during an incident, blocking state-mutating operations
+/// while reads keep working.
+pub struct SystemGuard;
+
+impl SystemGuard {
+    /// Store the guardian address
+    pub fn set_guardian(env: &Env, guardian: &Address) {
+        env.storage().instance().set(&Symbol::new(env, "guardian"), guardian);
+    }
+
+    /// Get the guardian address, if one is set
+    pub fn get_guardian(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&Symbol::new(env, "guardian"))
+    }
+
+    /// Pause or unpause the system (guardian only)
+    pub fn set_paused(env: &Env, guardian: Address, paused: bool) {
+        match Self::get_guardian(env) {
+            Some(stored) if stored == guardian => guardian.require_auth(),
+            _ => panic_with_error!(env, ContractError::Unauthorized),
+        }
+        env.storage().instance().set(&Symbol::new(env, "system_paused"), &paused);
+    }
+
+    /// Check if the system is paused
+    pub fn is_paused(env: &Env) -> bool {
+        env.storage().instance()
+            .get(&Symbol::new(env, "system_paused"))
+            .unwrap_or(false)
+    }
+
+    /// Panic if the system is paused
+    pub fn require_not_paused(env: &Env) {
+        if Self::is_paused(env) {
+            panic_with_error!(env, ContractError::SystemPaused);
+        }
+    }
+}
+
+/// Per-signer nonces that make off-chain signatures single-use
+///
+/// Signers sign `signed_payload(message, nonce)` with their current nonce;
+/// verification consumes the nonce, so replaying a signature fails.
+pub struct SignatureNonces;
+
+impl SignatureNonces {
+    /// Get the nonce the signer's next signature must use
+    pub fn get(env: &Env, signer: &BytesN<32>) -> u64 {
+        Self::all(env).get(signer.clone()).unwrap_or(0)
+    }
+
+    /// Build the payload a signer signs: the message followed by the big-endian nonce
+    pub fn signed_payload(env: &Env, message: &Bytes, nonce: u64) -> Bytes {
+        let mut payload = Bytes::new(env);
+        payload.append(message);
+        payload.extend_from_array(&nonce.to_be_bytes());
+        payload
+    }
+
+    /// Consume `nonce` for the signer, panicking with `InvalidInput` if it isn't current
+    pub fn consume(env: &Env, signer: &BytesN<32>, nonce: u64) {
+        let mut nonces = Self::all(env);
+        let expected = nonces.get(signer.clone()).unwrap_or(0);
+        if nonce != expected {
+            panic_with_error!(env, ContractError::InvalidInput);
+        }
+        nonces.set(signer.clone(), expected + 1);
+        env.storage().instance().set(&Symbol::new(env, "signature_nonces"), &nonces);
+    }
+
+    /// Verify an ed25519 signature over `message` and `nonce`, then consume the nonce
+    ///
+    /// Panics if the nonce is stale or reused, or if the signature is invalid.
+    pub fn verify(env: &Env, signer: &BytesN<32>, message: &Bytes, nonce: u64, signature: &BytesN<64>) {
+        Self::consume(env, signer, nonce);
+        env.crypto().ed25519_verify(signer, &Self::signed_payload(env, message, nonce), signature);
+    }
+
+    fn all(env: &Env) -> Map<BytesN<32>, u64> {
+        env.storage().instance()
+            .get(&Symbol::new(env, "signature_nonces"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+}
+
 /// Reentrancy guard to prevent recursive calls
 pub struct ReentrancyGuard {
     pub locked: bool,
@@ -147,6 +355,12 @@ impl ReentrancyGuard {
     }
 }
 
+impl Default for ReentrancyGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Time utilities
 pub mod time {
     use soroban_sdk::Env;
@@ -1,6 +1,8 @@
 //! Very simple insurance contract that demonstrates basic Soroban patterns
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, Symbol, Vec};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, Symbol, Vec, Bytes, panic_with_error};
+
+use crate::shared::{generate_id, next_id_nonce, ContractError};
 
 /// Policy data structure
 #[derive(Clone)]
@@ -19,9 +21,12 @@ pub struct SimpleInsurance;
 #[contractimpl]
 impl SimpleInsurance {
     /// Create a new policy
-    pub fn create_policy(env: Env, holder: Address, amount: i128) -> u32 {
-        // Simple ID generation - in production use proper hashing
-        let policy_id = env.ledger().sequence() as u32;
+    pub fn create_policy(env: Env, holder: Address, amount: i128) -> Bytes {
+        if amount <= 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let policy_id = generate_id(&env, Symbol::new(&env, "policy"), &holder, next_id_nonce(&env));
 
         // Create policy
         let policy = Policy {
@@ -31,38 +36,44 @@ impl SimpleInsurance {
         };
 
         // Store policy
-        let mut policies: Map<u32, Policy> = env.storage().instance()
+        let mut policies: Map<Bytes, Policy> = env.storage().instance()
             .get(&Symbol::new(&env, "POLICIES"))
             .unwrap_or(Map::new(&env));
 
-        policies.set(policy_id, policy.clone());
+        policies.set(policy_id.clone(), policy.clone());
         env.storage().instance().set(&Symbol::new(&env, "POLICIES"), &policies);
 
         // Add to user policies
-        let mut user_policies: Map<Address, Vec<u32>> = env.storage().instance()
+        let mut user_policies: Map<Address, Vec<Bytes>> = env.storage().instance()
             .get(&Symbol::new(&env, "USER_POLICIES"))
             .unwrap_or(Map::new(&env));
 
         let mut policies_vec = user_policies.get(holder.clone()).unwrap_or(Vec::new(&env));
-        policies_vec.push_back(policy_id);
+        policies_vec.push_back(policy_id.clone());
         user_policies.set(holder, policies_vec);
         env.storage().instance().set(&Symbol::new(&env, "USER_POLICIES"), &user_policies);
 
+        // Emit event
+        env.events().publish(
+            (Symbol::new(&env, "policy_created"), policy_id.clone()),
+            (policy.holder, policy.amount),
+        );
+
         policy_id
     }
 
     /// Get policy information
-    pub fn get_policy(env: Env, policy_id: u32) -> Policy {
-        let policies: Map<u32, Policy> = env.storage().instance()
+    pub fn get_policy(env: Env, policy_id: Bytes) -> Policy {
+        let policies: Map<Bytes, Policy> = env.storage().instance()
             .get(&Symbol::new(&env, "POLICIES"))
             .unwrap_or(Map::new(&env));
 
-        policies.get(policy_id).unwrap_or_else(|| panic!("Policy not found"))
+        policies.get(policy_id).unwrap_or_else(|| panic_with_error!(&env, ContractError::PolicyNotFound))
     }
 
     /// Get all policies for a user
-    pub fn get_user_policies(env: Env, user: Address) -> Vec<u32> {
-        let user_policies: Map<Address, Vec<u32>> = env.storage().instance()
+    pub fn get_user_policies(env: Env, user: Address) -> Vec<Bytes> {
+        let user_policies: Map<Address, Vec<Bytes>> = env.storage().instance()
             .get(&Symbol::new(&env, "USER_POLICIES"))
             .unwrap_or(Map::new(&env));
 
@@ -70,14 +81,20 @@ impl SimpleInsurance {
     }
 
     /// Deactivate a policy (simplified - anyone can deactivate for now)
-    pub fn deactivate_policy(env: Env, policy_id: u32) {
-        let mut policies: Map<u32, Policy> = env.storage().instance()
+    pub fn deactivate_policy(env: Env, policy_id: Bytes) {
+        let mut policies: Map<Bytes, Policy> = env.storage().instance()
             .get(&Symbol::new(&env, "POLICIES"))
             .unwrap_or(Map::new(&env));
 
-        let mut policy = policies.get(policy_id).unwrap_or_else(|| panic!("Policy not found"));
+        let mut policy = policies.get(policy_id.clone()).unwrap_or_else(|| panic_with_error!(&env, ContractError::PolicyNotFound));
         policy.active = false;
-        policies.set(policy_id, policy);
+        policies.set(policy_id.clone(), policy.clone());
         env.storage().instance().set(&Symbol::new(&env, "POLICIES"), &policies);
+
+        // Emit event
+        env.events().publish(
+            (Symbol::new(&env, "policy_deactivated"), policy_id),
+            (policy.holder, policy.amount),
+        );
     }
 }
\ No newline at end of file
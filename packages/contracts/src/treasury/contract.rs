@@ -1,32 +1,15 @@
 //! Treasury contract for multi-signature fund management
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec, Map, Symbol, Bytes, panic_with_error};
+use soroban_sdk::{contract, contractimpl, token, Address, Env, Vec, Map, String, Symbol, Bytes, panic_with_error};
 
-use crate::shared::{AccessControl, ContractError};
+use crate::shared::{require_nonzero_address, CONTRACT_VERSION, ContractError, InitGuard, Role, SystemGuard};
 use crate::treasury::{
-    types::{PendingTransfer, TransferStatus, TreasuryStats, TransferParams, FundAllocation},
+    types::{AdminInfo, ApprovalDelegation, ContractInfo, OwnerAction, PendingLimitIncrease, PendingOwnerAction, PendingTransfer, TransferStatus, TreasuryStats, TransferParams, FundAllocation, CategoryTotals, ApprovalTier},
 };
 
 /// Treasury contract for multi-signature fund management
-#[contracttype]
-pub struct Treasury {
-    /// Mapping from transfer ID to PendingTransfer
-    pending_transfers: Map<Bytes, PendingTransfer>,
-    /// Treasury statistics
-    stats: TreasuryStats,
-    /// Treasury owner address
-    owner: Address,
-    /// Authorized administrators
-    authorized_admins: Vec<Address>,
-    /// Fund allocation percentages
-    fund_allocation: FundAllocation,
-    /// Emergency shutdown status
-    emergency_shutdown: bool,
-    /// Maximum transfer amount without special approval
-    max_transfer_amount: i128,
-    /// Emergency transfer cooldown period
-    emergency_cooldown: u64,
-}
+#[contract]
+pub struct Treasury;
 
 #[contractimpl]
 impl Treasury {
@@ -35,19 +18,15 @@ impl Treasury {
     /// # Arguments
     /// * `owner` - Treasury owner address
     /// * `initial_admins` - Initial list of authorized administrators
-    pub fn __constructor(env: Env, owner: Address, initial_admins: Vec<Address>) {
-        let contract = Self {
-            pending_transfers: Map::new(env),
-            stats: TreasuryStats::new(),
-            owner,
-            authorized_admins: initial_admins.clone(),
-            fund_allocation: FundAllocation::default(),
-            emergency_shutdown: false,
-            max_transfer_amount: 10000, // $100 max without special approval
-            emergency_cooldown: 3600, // 1 hour cooldown
-        };
-
-        contract.initialize(env);
+    /// * `guardian` - Address allowed to pause the system
+    pub fn __constructor(env: Env, owner: Address, initial_admins: Vec<Address>, guardian: Address) {
+        InitGuard::initialize_once(&env);
+        SystemGuard::set_guardian(&env, &guardian);
+
+        env.storage().instance().set(&Symbol::new(&env, "owner"), &owner);
+        env.storage().instance().set(&Symbol::new(&env, "authorized_admins"), &initial_admins);
+        env.storage().instance().set(&Symbol::new(&env, "stats"), &TreasuryStats::new(&env));
+        Self::initialize(&env);
     }
 
     /// Submit a transfer for approval
@@ -57,174 +36,280 @@ impl Treasury {
     /// * `transfer_id` - Unique identifier for the transfer
     /// * `params` - Transfer parameters
     pub fn submit_transfer(env: Env, admin: Address, transfer_id: Bytes, params: TransferParams) {
-        Self::require_admin(&env, admin);
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_admin(&env, admin.clone());
 
         // Check if emergency shutdown is active
-        if Self::is_emergency_shutdown(&env) && !params.is_emergency {
+        if Self::is_emergency_shutdown(env.clone()) && !params.is_emergency {
             panic_with_error!(&env, ContractError::InvalidState);
         }
 
         // Check if transfer ID already exists
-        if env.storage().instance().has(&Symbol::new(&env, "pending_transfers"), &transfer_id) {
+        if env.storage().instance().has(&(Symbol::new(&env, "pending_transfers"), transfer_id.clone())) {
             panic_with_error!(&env, ContractError::InvalidInput);
         }
 
         // Validate transfer parameters
         Self::validate_transfer_params(&env, &params);
 
-        // Determine required approvals
+        // Determine required approvals (the default is already reduced for emergencies)
         let required_approvals = params.required_approvals
             .unwrap_or_else(|| Self::get_default_required_approvals(&env, &params));
 
-        // For emergency transfers, reduce approval requirements
-        let emergency_adjusted_approvals = if params.is_emergency {
-            (required_approvals + 1) / 2 // Halve approvals for emergencies
-        } else {
+        // First payouts to an address get an extra look, bounded by the admins available
+        let recipient_adjusted_approvals = if Self::is_known_recipient(env.clone(), params.to.clone()) {
             required_approvals
+        } else {
+            (required_approvals + Self::get_new_recipient_approvals(env.clone()))
+                .min(Self::get_authorized_admins(env.clone()).len().max(required_approvals))
         };
 
         // Create pending transfer
         let mut transfer = PendingTransfer::new(
             transfer_id.clone(),
             params.clone(),
-            emergency_adjusted_approvals,
+            recipient_adjusted_approvals,
             &env,
         );
 
-        // Auto-approve if the admin is the owner and amount is small enough
-        if admin == Self::get_owner(&env) && params.amount <= Self::get_max_transfer_amount(&env) {
-            transfer.add_approval(admin);
+        // Auto-approve if the admin is the owner and amount is within the auto-approve limit;
+        // the owner's approval alone is then sufficient
+        let auto_approved = admin == Self::get_owner(env.clone())
+            && params.amount <= Self::get_owner_auto_approve_limit(env.clone());
+        if auto_approved {
+            transfer.required_approvals = 1;
+            transfer.add_approval(admin.clone(), &env);
             transfer.mark_as_approved(&env);
         }
 
-        // Store the transfer
-        env.storage().instance().set(&Symbol::new(&env, "pending_transfers"), &transfer_id, &transfer);
+        // Persist the transfer and statistics before any auto-execution so
+        // execution only ever sees committed state
+        Self::store_pending_transfer(&env, &transfer_id, &transfer);
+        env.storage().instance().set(&(Symbol::new(&env, "transfer_submitters"), transfer_id.clone()), &admin);
 
-        // Update statistics
-        let mut stats = Self::get_stats(&env);
+        let mut stats = Self::get_stats(env.clone());
         stats.increment_pending_transfers();
         Self::set_stats(&env, stats);
 
         // Emit event
-        env.events().publish((
-            Symbol::new(&env, "transfer_submitted"),
-            transfer_id,
-            params.to,
-            params.amount,
-            params.reason,
-            transfer.required_approvals,
-            params.is_emergency,
-        ));
+        env.events().publish(
+            (Symbol::new(&env, "transfer_submitted"), transfer_id.clone()),
+            (
+                params.to,
+                params.amount,
+                params.reason,
+                params.category,
+                transfer.required_approvals,
+                params.is_emergency,
+            ),
+        );
 
-        // Auto-execute if already approved
-        if transfer.can_be_executed() {
-            Self::execute_transfer(env, transfer_id, admin);
+        // Auto-execute if already approved; owner auto-approved transfers move
+        // instantly, anything else must not be held back by the cooldown
+        if transfer.can_be_executed(&env, Self::get_approval_validity_period(env.clone()))
+            && (auto_approved || Self::is_cooldown_elapsed(&env, &transfer))
+        {
+            Self::execute_transfer_internal(&env, &admin, &transfer_id, auto_approved);
         }
     }
 
     /// Approve a pending transfer
     ///
     /// # Arguments
-    /// * `admin` - Administrator approving the transfer
+    /// * `approver` - Administrator, or an active delegate of one, approving the transfer
     /// * `transfer_id` - ID of the transfer to approve
     /// * `reason` - Reason for approval
-    pub fn approve_transfer(env: Env, admin: Address, transfer_id: Bytes, reason: Symbol) {
-        Self::require_admin(&env, admin);
+    pub fn approve_transfer(env: Env, approver: Address, transfer_id: Bytes, reason: Symbol) {
+        SystemGuard::require_not_paused(&env);
+
+        approver.require_auth();
+
+        // Active delegates approve on behalf of the administrator who delegated to them
+        let admin = Self::resolve_approver(&env, approver);
 
         // Get the pending transfer
-        let mut transfer = Self::get_pending_transfer(&env, &transfer_id);
+        let mut transfer = Self::get_pending_transfer(env.clone(), transfer_id.clone());
 
         // Check if transfer can still be approved
         if !transfer.is_pending() {
             panic_with_error!(&env, ContractError::InvalidState);
         }
 
-        // Check if admin has already approved
-        if transfer.has_approved(&admin) {
+        // Check if admin already has an unexpired approval (expired ones may be renewed)
+        let validity_period = Self::get_approval_validity_period(env.clone());
+        if transfer.has_approved(&admin, &env, validity_period) {
             panic_with_error!(&env, ContractError::TransferAlreadyAuthorized);
         }
 
         // Add approval
-        transfer.add_approval(admin);
+        transfer.add_approval(admin.clone(), &env);
+        if transfer.has_sufficient_approvals(&env, validity_period) {
+            transfer.mark_as_approved(&env);
+        }
 
-        // Update stored transfer
-        env.storage().instance().set(&Symbol::new(&env, "pending_transfers"), &transfer_id, &transfer);
+        // Update stored transfer (the pending count drops when it is executed)
+        Self::store_pending_transfer(&env, &transfer_id, &transfer);
 
-        // Update statistics
-        let mut stats = Self::get_stats(&env);
-        if transfer.has_sufficient_approvals() {
-            stats.decrement_pending_transfers();
+        // Emit event
+        env.events().publish(
+            (Symbol::new(&env, "transfer_approved"), transfer_id.clone()),
+            (
+                admin.clone(),
+                reason,
+                transfer.valid_approvals(&env, validity_period),
+                transfer.required_approvals,
+            ),
+        );
+
+        // Auto-execute if sufficient approvals and not held back by the cooldown
+        if transfer.can_be_executed(&env, validity_period) && Self::is_cooldown_elapsed(&env, &transfer) {
+            Self::execute_transfer_internal(&env, &admin, &transfer_id, false);
         }
-        Self::set_stats(&env, stats);
+    }
 
-        // Emit event
-        env.events().publish((
-            Symbol::new(&env, "transfer_approved"),
-            transfer_id,
-            admin,
-            reason,
-            transfer.approvals,
-            transfer.required_approvals,
-        ));
+    /// Temporarily delegate an administrator's approval authority
+    ///
+    /// Replaces any existing delegation from the same administrator.
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator delegating their approvals
+    /// * `delegate` - Address allowed to approve on the administrator's behalf
+    /// * `until` - Timestamp at which the delegation expires
+    pub fn delegate_approval(env: Env, admin: Address, delegate: Address, until: u64) {
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_admin(&env, admin.clone());
 
-        // Auto-execute if sufficient approvals
-        if transfer.can_be_executed() {
-            Self::execute_transfer(env, transfer_id, admin);
+        if delegate == admin || until <= env.ledger().timestamp() {
+            panic_with_error!(&env, ContractError::InvalidInput);
         }
+
+        let mut delegations = Self::get_delegations(&env);
+        delegations.set(admin.clone(), ApprovalDelegation { delegate: delegate.clone(), until });
+        env.storage().instance().set(&Symbol::new(&env, "approval_delegations"), &delegations);
+
+        env.events().publish(
+            (Symbol::new(&env, "approval_delegated"), admin),
+            (delegate, until),
+        );
+    }
+
+    /// Revoke an administrator's approval delegation
+    pub fn revoke_delegation(env: Env, admin: Address) {
+        Self::require_admin(&env, admin.clone());
+
+        let mut delegations = Self::get_delegations(&env);
+        delegations.remove(admin.clone());
+        env.storage().instance().set(&Symbol::new(&env, "approval_delegations"), &delegations);
+
+        env.events().publish((Symbol::new(&env, "approval_delegation_revoked"), admin), ());
+    }
+
+    /// Get an administrator's approval delegation, if any
+    pub fn get_delegation(env: Env, admin: Address) -> Option<ApprovalDelegation> {
+        Self::get_delegations(&env).get(admin)
     }
 
     /// Execute an approved transfer
     ///
+    /// Every transfer, emergency or not, waits out `emergency_cooldown` after
+    /// submission; emergency transfers can skip it only through `force_execute`.
+    ///
     /// # Arguments
     /// * `admin` - Administrator executing the transfer
     /// * `transfer_id` - ID of the transfer to execute
     pub fn execute_transfer(env: Env, admin: Address, transfer_id: Bytes) {
-        Self::require_admin(&env, admin);
+        SystemGuard::require_not_paused(&env);
 
-        // Get the pending transfer
-        let mut transfer = Self::get_pending_transfer(&env, &transfer_id);
+        Self::require_admin(&env, admin.clone());
+
+        Self::execute_transfer_internal(&env, &admin, &transfer_id, false);
+    }
 
-        // Check if transfer can be executed
-        if !transfer.can_be_executed() {
-            panic_with_error!(&env, ContractError::TransferNotAuthorized);
+    /// Confirm that an emergency transfer may bypass the cooldown (admin only)
+    ///
+    /// Confirmations are valid for `get_force_confirmation_window` seconds.
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator confirming the override
+    /// * `transfer_id` - ID of the emergency transfer
+    pub fn confirm_force_execute(env: Env, admin: Address, transfer_id: Bytes) {
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_admin(&env, admin.clone());
+
+        let transfer = Self::get_pending_transfer(env.clone(), transfer_id.clone());
+        if !transfer.is_emergency_transfer() {
+            panic_with_error!(&env, ContractError::InvalidState);
         }
 
-        // Check if emergency cooldown applies (for non-emergency transfers)
+        let mut confirmations = Self::get_force_confirmations(&env, &transfer_id);
+        confirmations.set(admin.clone(), env.ledger().timestamp());
+        env.storage().instance().set(&(Symbol::new(&env, "force_confirmations"), transfer_id.clone()), &confirmations);
+
+        env.events().publish(
+            (Symbol::new(&env, "force_execute_confirmed"), transfer_id),
+            admin,
+        );
+    }
+
+    /// Execute an emergency transfer before its cooldown has elapsed (owner only)
+    ///
+    /// Requires a confirmation from at least one other administrator within the
+    /// confirmation window. Non-emergency transfers can never bypass the cooldown.
+    ///
+    /// # Arguments
+    /// * `owner` - Treasury owner forcing the execution
+    /// * `transfer_id` - ID of the emergency transfer
+    pub fn force_execute(env: Env, owner: Address, transfer_id: Bytes) {
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_owner(&env, owner.clone());
+
+        let transfer = Self::get_pending_transfer(env.clone(), transfer_id.clone());
         if !transfer.is_emergency_transfer() {
-            let cooldown = Self::get_emergency_cooldown(&env);
-            if transfer.age(&env) < cooldown {
-                panic!("Transfer is within cooldown period");
-            }
+            panic_with_error!(&env, ContractError::InvalidState);
         }
 
-        // Check if treasury has sufficient balance
-        let treasury_balance = Self::get_treasury_balance(&env);
-        if treasury_balance < transfer.amount {
-            panic_with_error!(&env, ContractError::InsufficientBalance);
+        // Count confirmations from other admins still inside the window
+        let now = env.ledger().timestamp();
+        let window = Self::get_force_confirmation_window(env.clone());
+        let admins = Self::get_authorized_admins(env.clone());
+        let mut confirmations = 0;
+        for (admin, confirmed_at) in Self::get_force_confirmations(&env, &transfer_id).iter() {
+            if admin != owner && admins.contains(&admin) && now - confirmed_at <= window {
+                confirmations += 1;
+            }
+        }
+        if confirmations == 0 {
+            panic_with_error!(&env, ContractError::InsufficientApprovals);
         }
 
-        // Execute the transfer
-        // In production, this would involve actual fund transfer logic
-        transfer.mark_as_executed(&env);
+        Self::execute_transfer_internal(&env, &owner, &transfer_id, true);
+        env.storage().instance().remove(&(Symbol::new(&env, "force_confirmations"), transfer_id.clone()));
 
-        // Update statistics
-        let mut stats = Self::get_stats(&env);
-        stats.decrement_pending_transfers();
-        stats.increment_executed_transfers();
-        stats.transfer_funds("treasury", "external", transfer.amount);
-        Self::set_stats(&env, stats);
+        env.events().publish(
+            (Symbol::new(&env, "transfer_force_executed"), transfer_id),
+            (owner, confirmations),
+        );
+    }
 
-        // Remove from pending transfers
-        env.storage().instance().remove(&Symbol::new(&env, "pending_transfers"), &transfer_id);
+    /// Check whether a transfer can currently be executed
+    ///
+    /// Returns `(true, "executable")`, or `(false, reason)` where reason is one of
+    /// `insufficient_approvals`, `expired`, `in_cooldown`, `insufficient_balance`
+    /// or `emergency_limit`.
+    ///
+    /// # Arguments
+    /// * `transfer_id` - ID of the transfer to check
+    pub fn can_execute(env: Env, transfer_id: Bytes) -> (bool, Symbol) {
+        let transfer = Self::get_pending_transfer(env.clone(), transfer_id.clone());
 
-        // Emit event
-        env.events().publish((
-            Symbol::new(&env, "transfer_executed"),
-            transfer_id,
-            transfer.to,
-            transfer.amount,
-            admin,
-        ));
+        match Self::execution_blocker(&env, &transfer, false) {
+            Some(reason) => (false, Symbol::new(&env, reason)),
+            None => (true, Symbol::new(&env, "executable")),
+        }
     }
 
     /// Reject a pending transfer
@@ -234,10 +319,12 @@ impl Treasury {
     /// * `transfer_id` - ID of the transfer to reject
     /// * `reason` - Reason for rejection
     pub fn reject_transfer(env: Env, admin: Address, transfer_id: Bytes, reason: Symbol) {
-        Self::require_admin(&env, admin);
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_admin(&env, admin.clone());
 
         // Get the pending transfer
-        let mut transfer = Self::get_pending_transfer(&env, &transfer_id);
+        let mut transfer = Self::get_pending_transfer(env.clone(), transfer_id.clone());
 
         // Check if transfer can still be rejected
         if !transfer.is_pending() {
@@ -248,20 +335,84 @@ impl Treasury {
         transfer.mark_as_rejected(&env);
 
         // Update statistics
-        let mut stats = Self::get_stats(&env);
+        let mut stats = Self::get_stats(env.clone());
         stats.decrement_pending_transfers();
         Self::set_stats(&env, stats);
 
         // Remove from pending transfers
-        env.storage().instance().remove(&Symbol::new(&env, "pending_transfers"), &transfer_id);
+        Self::remove_pending_transfer(&env, &transfer_id);
 
         // Emit event
-        env.events().publish((
-            Symbol::new(&env, "transfer_rejected"),
-            transfer_id,
-            admin,
-            reason,
-        ));
+        env.events().publish(
+            (Symbol::new(&env, "transfer_rejected"), transfer_id),
+            (admin, reason),
+        );
+    }
+
+    /// Dispute a pending transfer, freezing approvals until the owner resolves it
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator contesting the transfer
+    /// * `transfer_id` - ID of the pending transfer
+    /// * `reason` - Reason for the dispute
+    pub fn dispute_transfer(env: Env, admin: Address, transfer_id: Bytes, reason: Symbol) {
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_admin(&env, admin.clone());
+
+        let mut transfer = Self::get_pending_transfer(env.clone(), transfer_id.clone());
+
+        // Only transfers still collecting approvals can be disputed
+        if !transfer.is_pending() {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        transfer.mark_as_disputed(&env);
+        Self::store_pending_transfer(&env, &transfer_id, &transfer);
+
+        env.events().publish(
+            (Symbol::new(&env, "transfer_disputed"), transfer_id),
+            (admin, reason),
+        );
+    }
+
+    /// Resolve a disputed transfer (owner only)
+    ///
+    /// Upholding the dispute rejects the transfer; overruling it returns the
+    /// transfer to `Pending` with its existing approvals intact.
+    ///
+    /// # Arguments
+    /// * `owner` - Treasury owner address
+    /// * `transfer_id` - ID of the disputed transfer
+    /// * `uphold` - Whether the dispute is upheld
+    pub fn resolve_dispute(env: Env, owner: Address, transfer_id: Bytes, uphold: bool) {
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_owner(&env, owner.clone());
+
+        let mut transfer = Self::get_pending_transfer(env.clone(), transfer_id.clone());
+
+        if !transfer.is_disputed() {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        if uphold {
+            transfer.mark_as_rejected(&env);
+
+            let mut stats = Self::get_stats(env.clone());
+            stats.decrement_pending_transfers();
+            Self::set_stats(&env, stats);
+
+            Self::remove_pending_transfer(&env, &transfer_id);
+        } else {
+            transfer.status = TransferStatus::Pending;
+            Self::store_pending_transfer(&env, &transfer_id, &transfer);
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "dispute_resolved"), transfer_id),
+            (owner, uphold),
+        );
     }
 
     /// Cancel a pending transfer
@@ -271,10 +422,12 @@ impl Treasury {
     /// * `transfer_id` - ID of the transfer to cancel
     /// * `reason` - Reason for cancellation
     pub fn cancel_transfer(env: Env, admin: Address, transfer_id: Bytes, reason: Symbol) {
-        Self::require_admin(&env, admin);
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_admin(&env, admin.clone());
 
         // Get the pending transfer
-        let mut transfer = Self::get_pending_transfer(&env, &transfer_id);
+        let mut transfer = Self::get_pending_transfer(env.clone(), transfer_id.clone());
 
         // Check if transfer can still be cancelled
         if !transfer.is_pending() {
@@ -283,7 +436,7 @@ impl Treasury {
 
         // Only the submitter or owner can cancel transfers
         let transfer_submitter = Self::get_transfer_submitter(&env, &transfer_id);
-        if admin != transfer_submitter && admin != Self::get_owner(&env) {
+        if admin != transfer_submitter && admin != Self::get_owner(env.clone()) {
             panic_with_error!(&env, ContractError::Unauthorized);
         }
 
@@ -291,20 +444,18 @@ impl Treasury {
         transfer.cancel(&env);
 
         // Update statistics
-        let mut stats = Self::get_stats(&env);
+        let mut stats = Self::get_stats(env.clone());
         stats.decrement_pending_transfers();
         Self::set_stats(&env, stats);
 
         // Remove from pending transfers
-        env.storage().instance().remove(&Symbol::new(&env, "pending_transfers"), &transfer_id);
+        Self::remove_pending_transfer(&env, &transfer_id);
 
         // Emit event
-        env.events().publish((
-            Symbol::new(&env, "transfer_cancelled"),
-            transfer_id,
-            admin,
-            reason,
-        ));
+        env.events().publish(
+            (Symbol::new(&env, "transfer_cancelled"), transfer_id),
+            (admin, reason),
+        );
     }
 
     /// Add funds to the treasury
@@ -314,27 +465,185 @@ impl Treasury {
     /// * `amount` - Amount to add
     /// * `reason` - Reason for the deposit
     pub fn add_funds(env: Env, from: Address, amount: i128, reason: Symbol) {
+        SystemGuard::require_not_paused(&env);
+
         if amount <= 0 {
             panic!("Amount must be positive");
         }
 
-        // Update statistics
-        let mut stats = Self::get_stats(&env);
+        // Add funds and rebalance in a single read-modify-write
+        let allocation = Self::get_fund_allocation(env.clone());
+        let mut stats = Self::get_stats(env.clone());
         stats.add_funds(amount);
-        Self::set_stats(&env, stats);
+        stats.rebalance_funds(&allocation);
+        Self::set_stats(&env, stats.clone());
+
+        // Emit event with the final stored values
+        env.events().publish(
+            (Symbol::new(&env, "funds_added"), from),
+            (
+                amount,
+                reason,
+                stats.total_balance,
+                stats.insurance_fund_balance,
+                stats.operational_fund_balance,
+                stats.emergency_fund_balance,
+            ),
+        );
+    }
 
-        // Rebalance funds according to allocation
-        stats.rebalance_funds(&Self::get_fund_allocation(&env));
-        Self::set_stats(&env, stats);
+    /// Record protocol fee revenue routed from the yield aggregator
+    ///
+    /// # Arguments
+    /// * `from` - Registered yield aggregator routing the fees
+    /// * `amount` - Fee amount
+    pub fn collect_protocol_fees(env: Env, from: Address, amount: i128) {
+        SystemGuard::require_not_paused(&env);
+
+        from.require_auth();
+
+        if Self::get_yield_aggregator(env.clone()) != Some(from.clone()) {
+            panic_with_error!(&env, ContractError::Unauthorized);
+        }
+
+        if amount <= 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let mut stats = Self::get_stats(env.clone());
+        stats.add_protocol_fees(amount);
+        Self::set_stats(&env, stats.clone());
+
+        env.events().publish(
+            (Symbol::new(&env, "protocol_fees_collected"), from),
+            (amount, stats.protocol_fees_collected),
+        );
+    }
+
+    /// Claim accumulated protocol fees (owner only)
+    ///
+    /// Only draws from the protocol fee pool; user-owed funds are never touched.
+    ///
+    /// # Arguments
+    /// * `owner` - Treasury owner address
+    /// * `to` - Recipient of the fees
+    /// * `amount` - Amount to claim
+    pub fn claim_protocol_fees(env: Env, owner: Address, to: Address, amount: i128) {
+        SystemGuard::require_not_paused(&env);
+
+        Self::require_owner(&env, owner.clone());
+
+        if amount <= 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let mut stats = Self::get_stats(env.clone());
+        if amount > stats.protocol_fees_collected {
+            panic_with_error!(&env, ContractError::InsufficientBalance);
+        }
+
+        stats.remove_protocol_fees(amount);
+        Self::set_stats(&env, stats.clone());
 
         // Emit event
-        env.events().publish((
-            Symbol::new(&env, "funds_added"),
-            from,
-            amount,
-            reason,
-            stats.total_balance,
-        ));
+        env.events().publish(
+            (Symbol::new(&env, "protocol_fees_claimed"), owner),
+            (to, amount, stats.protocol_fees_collected),
+        );
+    }
+
+    /// Draw from the emergency fund on behalf of the insurance contract
+    ///
+    /// # Arguments
+    /// * `caller` - Registered emergency drawer (the insurance contract)
+    /// * `amount` - Amount to draw
+    pub fn draw_emergency_funds(env: Env, caller: Address, amount: i128) {
+        SystemGuard::require_not_paused(&env);
+
+        caller.require_auth();
+
+        if Self::get_emergency_drawer(env.clone()) != Some(caller.clone()) {
+            panic_with_error!(&env, ContractError::Unauthorized);
+        }
+
+        if amount <= 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let mut stats = Self::get_stats(env.clone());
+        if amount > stats.emergency_fund_balance {
+            panic_with_error!(&env, ContractError::InsufficientBalance);
+        }
+
+        stats.remove_emergency_funds(amount);
+        Self::set_stats(&env, stats.clone());
+
+        // In production, this would transfer `amount` to the caller
+        env.events().publish(
+            (Symbol::new(&env, "emergency_funds_drawn"), caller),
+            (amount, stats.emergency_fund_balance),
+        );
+    }
+
+    /// Deposit a supported asset into the treasury
+    ///
+    /// # Arguments
+    /// * `from` - Address sending the funds
+    /// * `asset` - Token address of a supported asset
+    /// * `amount` - Amount to deposit
+    pub fn deposit_asset(env: Env, from: Address, asset: Address, amount: i128) {
+        SystemGuard::require_not_paused(&env);
+
+        from.require_auth();
+
+        if amount <= 0 || !Self::get_assets(env.clone()).contains(&asset) {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        token::Client::new(&env, &asset).transfer(&from, &env.current_contract_address(), &amount);
+
+        let mut stats = Self::get_stats(env.clone());
+        stats.add_asset_funds(&asset, amount);
+        Self::set_stats(&env, stats.clone());
+
+        env.events().publish(
+            (Symbol::new(&env, "asset_deposited"), from),
+            (asset.clone(), amount, stats.asset_balance(&asset)),
+        );
+    }
+
+    /// Reconcile the recorded balance against the treasury's real token balance (admin only)
+    ///
+    /// Any drift is reported through a `reconciliation_discrepancy` event before
+    /// the recorded balance is corrected and the sub-funds are rebalanced to the
+    /// current allocation. Returns the delta (actual - recorded).
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator running the reconciliation
+    pub fn reconcile(env: Env, admin: Address) -> i128 {
+        Self::require_admin(&env, admin.clone());
+
+        let token_address = Self::get_token(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::InvalidState));
+        let actual_balance = token::Client::new(&env, &token_address)
+            .balance(&env.current_contract_address());
+
+        let mut stats = Self::get_stats(env.clone());
+        let recorded_balance = stats.total_balance;
+        let delta = actual_balance - recorded_balance;
+
+        if delta != 0 {
+            env.events().publish(
+                (Symbol::new(&env, "reconciliation_discrepancy"), admin),
+                (recorded_balance, actual_balance, delta),
+            );
+
+            stats.total_balance = actual_balance;
+            stats.rebalance_funds(&Self::get_fund_allocation(env.clone()));
+            Self::set_stats(&env, stats);
+        }
+
+        delta
     }
 
     /// Enable emergency shutdown (owner only)
@@ -343,16 +652,9 @@ impl Treasury {
     /// * `owner` - Treasury owner address
     /// * `reason` - Reason for emergency shutdown
     pub fn emergency_shutdown(env: Env, owner: Address, reason: Symbol) {
-        Self::require_owner(&env, owner);
+        Self::require_owner(&env, owner.clone());
 
-        env.storage().instance().set(&Symbol::new(&env, "emergency_shutdown"), &true);
-
-        // Emit event
-        env.events().publish((
-            Symbol::new(&env, "emergency_shutdown"),
-            owner,
-            reason,
-        ));
+        Self::run_owner_action(&env, owner, OwnerAction::EmergencyShutdown(reason));
     }
 
     /// Disable emergency shutdown (owner only)
@@ -361,16 +663,97 @@ impl Treasury {
     /// * `owner` - Treasury owner address
     /// * `reason` - Reason for disabling shutdown
     pub fn disable_emergency_shutdown(env: Env, owner: Address, reason: Symbol) {
-        Self::require_owner(&env, owner);
+        Self::require_owner(&env, owner.clone());
 
         env.storage().instance().set(&Symbol::new(&env, "emergency_shutdown"), &false);
 
         // Emit event
-        env.events().publish((
-            Symbol::new(&env, "emergency_shutdown_disabled"),
-            owner,
+        env.events().publish(
+            (Symbol::new(&env, "emergency_shutdown_disabled"), owner),
             reason,
-        ));
+        );
+    }
+
+    /// Get the pre-configured address emergency sweeps send funds to, if any
+    pub fn get_emergency_beneficiary(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "emergency_beneficiary"))
+            .unwrap_or(None)
+    }
+
+    /// Set the emergency sweep address (owner only)
+    ///
+    /// Cannot be changed while emergency shutdown is active, so the destination
+    /// is always fixed before an incident.
+    pub fn update_emergency_beneficiary(env: Env, owner: Address, beneficiary: Address) {
+        Self::require_owner(&env, owner.clone());
+
+        if Self::is_emergency_shutdown(env.clone()) {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+        require_nonzero_address(&env, &beneficiary);
+
+        env.storage().instance().set(&Symbol::new(&env, "emergency_beneficiary"), &Some(beneficiary.clone()));
+
+        env.events().publish(
+            (Symbol::new(&env, "emergency_beneficiary_updated"), owner),
+            beneficiary,
+        );
+    }
+
+    /// Sweep the entire treasury to the emergency beneficiary (owner only)
+    ///
+    /// Only available during emergency shutdown. `to` must match the
+    /// pre-configured beneficiary; the destination can't be chosen at call time.
+    /// Deliberately not blocked by the system pause.
+    ///
+    /// # Arguments
+    /// * `owner` - Treasury owner address
+    /// * `to` - Expected emergency beneficiary
+    pub fn emergency_sweep(env: Env, owner: Address, to: Address) -> i128 {
+        Self::require_owner(&env, owner.clone());
+
+        if !Self::is_emergency_shutdown(env.clone()) {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        let beneficiary = Self::get_emergency_beneficiary(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::InvalidState));
+        if to != beneficiary {
+            panic_with_error!(&env, ContractError::Unauthorized);
+        }
+
+        let contract_address = env.current_contract_address();
+
+        // Sweep the primary token
+        let mut swept = 0;
+        if let Some(token_address) = Self::get_token(env.clone()) {
+            let token_client = token::Client::new(&env, &token_address);
+            swept = token_client.balance(&contract_address);
+            if swept > 0 {
+                token_client.transfer(&contract_address, &beneficiary, &swept);
+            }
+        }
+
+        // Sweep every supported asset
+        for asset in Self::get_assets(env.clone()).iter() {
+            let asset_client = token::Client::new(&env, &asset);
+            let balance = asset_client.balance(&contract_address);
+            if balance > 0 {
+                asset_client.transfer(&contract_address, &beneficiary, &balance);
+            }
+        }
+
+        let mut stats = Self::get_stats(env.clone());
+        stats.clear_balances(&env);
+        Self::set_stats(&env, stats);
+
+        env.events().publish(
+            (Symbol::new(&env, "emergency_sweep"), owner),
+            (beneficiary, swept),
+        );
+
+        swept
     }
 
     /// Update fund allocation percentages (owner only)
@@ -379,57 +762,395 @@ impl Treasury {
     /// * `owner` - Treasury owner address
     /// * `allocation` - New fund allocation
     pub fn update_fund_allocation(env: Env, owner: Address, allocation: FundAllocation) {
-        Self::require_owner(&env, owner);
+        Self::require_owner(&env, owner.clone());
 
         // Validate allocation percentages
         if allocation.insurance_percentage + allocation.operational_percentage + allocation.emergency_percentage != 100 {
             panic!("Allocation percentages must sum to 100");
         }
 
-        env.storage().instance().set(&Symbol::new(&env, "fund_allocation"), &allocation);
+        Self::run_owner_action(&env, owner, OwnerAction::UpdateFundAllocation(allocation));
+    }
 
-        // Rebalance funds according to new allocation
-        let mut stats = Self::get_stats(&env);
-        stats.rebalance_funds(&allocation);
-        Self::set_stats(&env, stats);
+    /// Check whether owner-only actions need an administrator co-signature
+    pub fn get_owner_cosign_required(env: Env) -> bool {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "owner_cosign_required"))
+            .unwrap_or(false)
+    }
 
-        // Emit event
-        env.events().publish((
-            Symbol::new(&env, "fund_allocation_updated"),
-            allocation.insurance_percentage,
-            allocation.operational_percentage,
-            allocation.emergency_percentage,
-        ));
+    /// Require an administrator co-signature on owner-only actions (owner only)
+    ///
+    /// Enabling takes effect immediately. Disabling is itself an owner action and
+    /// needs a co-signature while the requirement is on.
+    pub fn set_owner_cosign_required(env: Env, owner: Address, required: bool) {
+        Self::require_owner(&env, owner.clone());
+
+        if required {
+            env.storage().instance().set(&Symbol::new(&env, "owner_cosign_required"), &true);
+            env.events().publish(
+                (Symbol::new(&env, "owner_cosign_updated"), owner),
+                true,
+            );
+        } else if Self::get_owner_cosign_required(env.clone()) {
+            Self::queue_owner_action(&env, owner, OwnerAction::DisableOwnerCosign);
+        }
     }
 
-    /// Get pending transfer information
-    pub fn get_pending_transfer(env: Env, transfer_id: Bytes) -> PendingTransfer {
+    /// Get how long a queued owner action can be co-signed, in seconds
+    pub fn get_owner_cosign_window(env: Env) -> u64 {
         env.storage().instance()
-            .get(&Symbol::new(&env, "pending_transfers"), &transfer_id)
-            .unwrap_or_else(|| panic!("Transfer not found"))
+            .get(&Symbol::new(&env, "owner_cosign_window"))
+            .unwrap_or(86400)
     }
 
-    /// Get all pending transfers
-    pub fn get_all_pending_transfers(env: Env) -> Vec<Bytes> {
-        let mut transfer_ids = Vec::new(&env);
+    /// Update the owner action co-signing window (owner only)
+    pub fn update_owner_cosign_window(env: Env, owner: Address, window_seconds: u64) {
+        Self::require_owner(&env, owner.clone());
 
-        // This is simplified - in production, use more efficient iteration
-        env.storage().instance()
-            .has(&Symbol::new(&env, "pending_transfers"))
+        if window_seconds == 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "owner_cosign_window"), &window_seconds);
     }
 
-    /// Get treasury statistics
-    pub fn get_stats(env: Env) -> TreasuryStats {
-        env.storage().instance()
-            .get(&Symbol::new(&env, "stats"))
-            .unwrap_or_else(|| TreasuryStats::new())
+    /// Get a queued owner action, if it exists
+    pub fn get_pending_owner_action(env: Env, action_id: u32) -> Option<PendingOwnerAction> {
+        env.storage().instance().get(&(Symbol::new(&env, "pending_owner_actions"), action_id))
     }
 
-    /// Get treasury owner
-    pub fn get_owner(env: Env) -> Address {
+    /// Co-sign and apply a queued owner action (admin only)
+    ///
+    /// The co-signer must be an administrator other than the owner, and must
+    /// sign within the co-signing window.
+    ///
+    /// # Arguments
+    /// * `admin` - Administrator co-signing the action
+    /// * `action_id` - ID returned when the action was queued
+    pub fn cosign_owner_action(env: Env, admin: Address, action_id: u32) {
+        Self::require_admin(&env, admin.clone());
+
+        if admin == Self::get_owner(env.clone()) {
+            panic_with_error!(&env, ContractError::Unauthorized);
+        }
+
+        let pending = Self::get_pending_owner_action(env.clone(), action_id)
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound));
+        env.storage().instance().remove(&(Symbol::new(&env, "pending_owner_actions"), action_id));
+
+        if pending.is_expired(&env, Self::get_owner_cosign_window(env.clone())) {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "owner_action_cosigned"), action_id),
+            admin,
+        );
+
+        Self::apply_owner_action(&env, pending.action);
+    }
+
+    /// Pause all state-mutating operations (guardian only)
+    pub fn pause_system(env: Env, guardian: Address) {
+        SystemGuard::set_paused(&env, guardian.clone(), true);
+        env.events().publish((Symbol::new(&env, "system_paused"), guardian), ());
+    }
+
+    /// Resume state-mutating operations (guardian only)
+    pub fn unpause_system(env: Env, guardian: Address) {
+        SystemGuard::set_paused(&env, guardian.clone(), false);
+        env.events().publish((Symbol::new(&env, "system_unpaused"), guardian), ());
+    }
+
+    /// Check if the system is paused
+    pub fn is_system_paused(env: Env) -> bool {
+        SystemGuard::is_paused(&env)
+    }
+
+    /// Get pending transfer information
+    pub fn get_pending_transfer(env: Env, transfer_id: Bytes) -> PendingTransfer {
+        env.storage().instance()
+            .get(&(Symbol::new(&env, "pending_transfers"), transfer_id.clone()))
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound))
+    }
+
+    /// Get all pending transfers
+    pub fn get_all_pending_transfers(env: Env) -> Vec<Bytes> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "pending_transfer_ids"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get treasury statistics
+    pub fn get_stats(env: Env) -> TreasuryStats {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "stats"))
+            .unwrap_or_else(|| TreasuryStats::new(&env))
+    }
+
+    /// Get how long an approval counts toward a transfer's threshold, in seconds
+    pub fn get_approval_validity_period(env: Env) -> u64 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "approval_validity_period"))
+            .unwrap_or(604800)
+    }
+
+    /// Update how long approvals stay valid (owner only)
+    pub fn update_approval_validity_period(env: Env, owner: Address, validity_period: u64) {
+        Self::require_owner(&env, owner.clone());
+
+        if validity_period == 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "approval_validity_period"), &validity_period);
+    }
+
+    /// Get the assets the treasury supports
+    pub fn get_assets(env: Env) -> Vec<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "assets"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get the balance held in a supported asset
+    pub fn get_asset_balance(env: Env, asset: Address) -> i128 {
+        Self::get_stats(env.clone()).asset_balance(&asset)
+    }
+
+    /// Add a supported asset, such as a SAC token or the native XLM SAC (owner only)
+    pub fn add_asset(env: Env, owner: Address, asset: Address) {
+        Self::require_owner(&env, owner.clone());
+
+        let mut assets = Self::get_assets(env.clone());
+        if !assets.contains(&asset) {
+            assets.push_back(asset);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "assets"), &assets);
+    }
+
+    /// Remove a supported asset with no remaining balance (owner only)
+    pub fn remove_asset(env: Env, owner: Address, asset: Address) {
+        Self::require_owner(&env, owner.clone());
+
+        if Self::get_asset_balance(env.clone(), asset.clone()) > 0 {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        let mut assets = Self::get_assets(env.clone());
+        let mut i = 0;
+        while i < assets.len() {
+            if assets.get(i).unwrap() == asset {
+                assets.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "assets"), &assets);
+    }
+
+    /// Get the token the treasury holds, if configured
+    pub fn get_token(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "token"))
+            .unwrap_or(None)
+    }
+
+    /// Set the token the treasury holds (owner only)
+    pub fn update_token(env: Env, owner: Address, token: Address) {
+        Self::require_owner(&env, owner.clone());
+        Self::run_owner_action(&env, owner, OwnerAction::UpdateToken(token));
+    }
+
+    /// Get the contract allowed to draw from the emergency fund, if any
+    pub fn get_emergency_drawer(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "emergency_drawer"))
+            .unwrap_or(None)
+    }
+
+    /// Set or clear the contract allowed to draw from the emergency fund (owner only)
+    pub fn update_emergency_drawer(env: Env, owner: Address, drawer: Option<Address>) {
+        Self::require_owner(&env, owner.clone());
+        Self::run_owner_action(&env, owner, OwnerAction::UpdateEmergencyDrawer(drawer));
+    }
+
+    /// Get the yield aggregator allowed to route protocol fees, if any
+    pub fn get_yield_aggregator(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "yield_aggregator"))
+            .unwrap_or(None)
+    }
+
+    /// Set or clear the yield aggregator allowed to route protocol fees (owner only)
+    pub fn update_yield_aggregator(env: Env, owner: Address, yield_aggregator: Option<Address>) {
+        Self::require_owner(&env, owner.clone());
+        env.storage().instance().set(&Symbol::new(&env, "yield_aggregator"), &yield_aggregator);
+    }
+
+    /// Get protocol fee revenue available to claim
+    pub fn get_protocol_fees_collected(env: Env) -> i128 {
+        Self::get_stats(env.clone()).protocol_fees_collected
+    }
+
+    /// Get executed transfer totals grouped by category
+    pub fn get_category_totals(env: Env) -> CategoryTotals {
+        Self::get_stats(env.clone()).category_totals
+    }
+
+    /// Get the contract version and headline configuration
+    ///
+    /// Lets clients check compatibility with the deployed contract.
+    pub fn contract_info(env: Env) -> ContractInfo {
+        ContractInfo {
+            version: String::from_str(&env, CONTRACT_VERSION),
+            owner: Self::get_owner(env.clone()),
+            admin_count: Self::get_authorized_admins(env.clone()).len(),
+            max_transfer_amount: Self::get_max_transfer_amount(env.clone()),
+            emergency_cooldown: Self::get_emergency_cooldown(env.clone()),
+            emergency_shutdown: Self::is_emergency_shutdown(env.clone()),
+        }
+    }
+
+    /// Get the treasury's total balance across all funds
+    pub fn get_total_balance(env: Env) -> i128 {
+        Self::get_treasury_balance(&env)
+    }
+
+    /// Get how often the owner must check in, in seconds (0 disables the heartbeat)
+    pub fn get_heartbeat_interval(env: Env) -> u64 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "heartbeat_interval"))
+            .unwrap_or(0)
+    }
+
+    /// Get the timestamp of the owner's last heartbeat
+    pub fn get_last_heartbeat(env: Env) -> u64 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "last_heartbeat"))
+            .unwrap_or(0)
+    }
+
+    /// Get the admin allowed to start ownership recovery after a missed heartbeat, if any
+    pub fn get_recovery_admin(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "recovery_admin"))
+            .unwrap_or(None)
+    }
+
+    /// Get the proposed owner of an in-progress recovery, if any
+    pub fn get_pending_owner_recovery(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "pending_owner_recovery"))
+            .unwrap_or(None)
+    }
+
+    /// Check whether the owner has missed the heartbeat deadline
+    pub fn is_heartbeat_missed(env: Env) -> bool {
+        let interval = Self::get_heartbeat_interval(env.clone());
+        interval > 0 && env.ledger().timestamp() > Self::get_last_heartbeat(env.clone()) + interval
+    }
+
+    /// Update the heartbeat interval and recovery admin (owner only)
+    ///
+    /// Counts as a heartbeat, so enabling it never starts out missed.
+    ///
+    /// # Arguments
+    /// * `owner` - Treasury owner
+    /// * `interval` - Maximum time between heartbeats in seconds (0 disables)
+    /// * `recovery_admin` - Admin allowed to start recovery once a heartbeat is missed
+    pub fn update_heartbeat(env: Env, owner: Address, interval: u64, recovery_admin: Address) {
+        Self::require_owner(&env, owner.clone());
+
+        require_nonzero_address(&env, &recovery_admin);
+        if recovery_admin == owner {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "heartbeat_interval"), &interval);
+        env.storage().instance().set(&Symbol::new(&env, "recovery_admin"), &Some(recovery_admin.clone()));
+        env.storage().instance().set(&Symbol::new(&env, "last_heartbeat"), &env.ledger().timestamp());
+
+        env.events().publish(
+            (Symbol::new(&env, "heartbeat_updated"), owner),
+            (interval, recovery_admin),
+        );
+    }
+
+    /// Record owner activity (owner only)
+    ///
+    /// Also cancels any ownership recovery in progress.
+    pub fn heartbeat(env: Env, owner: Address) {
+        Self::require_owner(&env, owner.clone());
+
+        let now = env.ledger().timestamp();
+        env.storage().instance().set(&Symbol::new(&env, "last_heartbeat"), &now);
+
+        if Self::get_pending_owner_recovery(env.clone()).is_some() {
+            env.storage().instance().remove(&Symbol::new(&env, "pending_owner_recovery"));
+            env.events().publish((Symbol::new(&env, "owner_recovery_cancelled"), owner.clone()), ());
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "heartbeat"), owner),
+            now,
+        );
+    }
+
+    /// Propose a new owner after a missed heartbeat (recovery admin only)
+    ///
+    /// The proposed owner must accept with `accept_owner_recovery`. A heartbeat
+    /// from the current owner before then cancels the recovery.
+    pub fn initiate_owner_recovery(env: Env, recovery_admin: Address, new_owner: Address) {
+        if Self::get_recovery_admin(env.clone()) != Some(recovery_admin.clone()) {
+            panic_with_error!(&env, ContractError::Unauthorized);
+        }
+        recovery_admin.require_auth();
+
+        if !Self::is_heartbeat_missed(env.clone()) {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+        require_nonzero_address(&env, &new_owner);
+
+        env.storage().instance().set(&Symbol::new(&env, "pending_owner_recovery"), &Some(new_owner.clone()));
+
+        env.events().publish(
+            (Symbol::new(&env, "owner_recovery_initiated"), recovery_admin),
+            (Self::get_owner(env.clone()), new_owner),
+        );
+    }
+
+    /// Complete an ownership recovery (proposed owner only)
+    pub fn accept_owner_recovery(env: Env, new_owner: Address) {
+        if Self::get_pending_owner_recovery(env.clone()) != Some(new_owner.clone()) {
+            panic_with_error!(&env, ContractError::Unauthorized);
+        }
+        new_owner.require_auth();
+
+        // The owner may have come back since recovery started
+        if !Self::is_heartbeat_missed(env.clone()) {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        let previous_owner = Self::get_owner(env.clone());
+        env.storage().instance().set(&Symbol::new(&env, "owner"), &new_owner);
+        env.storage().instance().remove(&Symbol::new(&env, "pending_owner_recovery"));
+        env.storage().instance().set(&Symbol::new(&env, "last_heartbeat"), &env.ledger().timestamp());
+
+        env.events().publish(
+            (Symbol::new(&env, "owner_recovered"), previous_owner),
+            new_owner,
+        );
+    }
+
+    /// Get treasury owner
+    pub fn get_owner(env: Env) -> Address {
         env.storage().instance()
             .get(&Symbol::new(&env, "owner"))
-            .unwrap_or_else(|| panic!("Owner not set"))
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotInitialized))
     }
 
     /// Get authorized administrators
@@ -439,11 +1160,66 @@ impl Treasury {
             .unwrap_or_else(|| Vec::new(&env))
     }
 
+    /// Get every authorized administrator with their role bits and weight
+    pub fn get_admin_details(env: Env) -> Vec<AdminInfo> {
+        let roles: Map<Address, u32> = env.storage().instance()
+            .get(&Symbol::new(&env, "admin_roles"))
+            .unwrap_or_else(|| Map::new(&env));
+        let weights: Map<Address, u32> = env.storage().instance()
+            .get(&Symbol::new(&env, "admin_weights"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut details = Vec::new(&env);
+        for admin in Self::get_authorized_admins(env.clone()).iter() {
+            details.push_back(AdminInfo {
+                roles: roles.get(admin.clone()).unwrap_or(Role::Admin as u32),
+                weight: weights.get(admin.clone()).unwrap_or(1),
+                address: admin,
+            });
+        }
+
+        details
+    }
+
+    /// Set the role bitmask of an administrator (owner only)
+    pub fn set_admin_roles(env: Env, owner: Address, admin: Address, roles: u32) {
+        Self::require_owner(&env, owner.clone());
+
+        if !Self::get_authorized_admins(env.clone()).contains(&admin) {
+            panic_with_error!(&env, ContractError::NotFound);
+        }
+
+        let mut admin_roles: Map<Address, u32> = env.storage().instance()
+            .get(&Symbol::new(&env, "admin_roles"))
+            .unwrap_or_else(|| Map::new(&env));
+        admin_roles.set(admin, roles);
+        env.storage().instance().set(&Symbol::new(&env, "admin_roles"), &admin_roles);
+    }
+
+    /// Set the approval weight of an administrator (owner only)
+    pub fn set_admin_weight(env: Env, owner: Address, admin: Address, weight: u32) {
+        Self::require_owner(&env, owner.clone());
+
+        if !Self::get_authorized_admins(env.clone()).contains(&admin) {
+            panic_with_error!(&env, ContractError::NotFound);
+        }
+
+        if weight == 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let mut admin_weights: Map<Address, u32> = env.storage().instance()
+            .get(&Symbol::new(&env, "admin_weights"))
+            .unwrap_or_else(|| Map::new(&env));
+        admin_weights.set(admin, weight);
+        env.storage().instance().set(&Symbol::new(&env, "admin_weights"), &admin_weights);
+    }
+
     /// Get fund allocation percentages
     pub fn get_fund_allocation(env: Env) -> FundAllocation {
         env.storage().instance()
             .get(&Symbol::new(&env, "fund_allocation"))
-            .unwrap_or_else(|| FundAllocation::default())
+            .unwrap_or_default()
     }
 
     /// Get maximum transfer amount
@@ -453,6 +1229,34 @@ impl Treasury {
             .unwrap_or(10000)
     }
 
+    /// Get minimum transfer amount (0 means no minimum)
+    pub fn get_min_transfer_amount(env: Env) -> i128 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "min_transfer_amount"))
+            .unwrap_or(0)
+    }
+
+    /// Get number of extra approvals required for transfers to first-time recipients
+    pub fn get_new_recipient_approvals(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "new_recipient_extra_approvals"))
+            .unwrap_or(0)
+    }
+
+    /// Check whether a transfer to this address has been executed before
+    pub fn is_known_recipient(env: Env, recipient: Address) -> bool {
+        env.storage().instance()
+            .get(&(Symbol::new(&env, "paid_recipients"), recipient.clone()))
+            .unwrap_or(false)
+    }
+
+    /// Get maximum amount the owner can move without collecting other approvals
+    pub fn get_owner_auto_approve_limit(env: Env) -> i128 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "owner_auto_approve_limit"))
+            .unwrap_or(10000)
+    }
+
     /// Get emergency cooldown period
     pub fn get_emergency_cooldown(env: Env) -> u64 {
         env.storage().instance()
@@ -460,6 +1264,63 @@ impl Treasury {
             .unwrap_or(3600)
     }
 
+    /// Get how long a force-execution confirmation stays valid, in seconds
+    pub fn get_force_confirmation_window(env: Env) -> u64 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "force_confirmation_window"))
+            .unwrap_or(3600)
+    }
+
+    /// Update the force-execution confirmation window (owner only)
+    pub fn update_force_confirmation_window(env: Env, owner: Address, window_seconds: u64) {
+        Self::require_owner(&env, owner.clone());
+        Self::run_owner_action(&env, owner, OwnerAction::UpdateForceConfirmationWindow(window_seconds));
+    }
+
+    /// Get the maximum total executed by emergency transfers per window (0 disables)
+    pub fn get_emergency_limit(env: Env) -> i128 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "emergency_limit"))
+            .unwrap_or(0)
+    }
+
+    /// Get the length of the emergency transfer limit window, in seconds
+    pub fn get_emergency_limit_window(env: Env) -> u64 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "emergency_limit_window"))
+            .unwrap_or(86400)
+    }
+
+    /// Get the amount executed by emergency transfers in the current window
+    pub fn get_emergency_spent(env: Env) -> i128 {
+        let (window_start, spent): (u64, i128) = env.storage().instance()
+            .get(&Symbol::new(&env, "emergency_spent"))
+            .unwrap_or((0, 0));
+
+        if env.ledger().timestamp() - window_start >= Self::get_emergency_limit_window(env.clone()) {
+            0
+        } else {
+            spent
+        }
+    }
+
+    /// Update the emergency transfer limit and its window (owner only)
+    ///
+    /// # Arguments
+    /// * `owner` - Treasury owner address
+    /// * `limit` - Maximum total emergency transfers per window (0 disables)
+    /// * `window_seconds` - Length of the window
+    pub fn update_emergency_limit(env: Env, owner: Address, limit: i128, window_seconds: u64) {
+        Self::require_owner(&env, owner.clone());
+
+        if limit < 0 || window_seconds == 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "emergency_limit"), &limit);
+        env.storage().instance().set(&Symbol::new(&env, "emergency_limit_window"), &window_seconds);
+    }
+
     /// Check if emergency shutdown is active
     pub fn is_emergency_shutdown(env: Env) -> bool {
         env.storage().instance()
@@ -467,40 +1328,292 @@ impl Treasury {
             && env.storage().instance().get(&Symbol::new(&env, "emergency_shutdown")).unwrap()
     }
 
+    /// Update minimum transfer amount (owner only)
+    ///
+    /// Keeps dust transfers out of the approval queue. Zero disables the minimum.
+    pub fn update_min_transfer_amount(env: Env, owner: Address, amount: i128) {
+        Self::require_owner(&env, owner.clone());
+
+        if amount < 0 || amount > Self::get_max_transfer_amount(env.clone()) {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "min_transfer_amount"), &amount);
+
+        env.events().publish(
+            (Symbol::new(&env, "min_transfer_amount_updated"), owner),
+            amount,
+        );
+    }
+
+    /// Update extra approvals required for first-time recipients (owner only)
+    ///
+    /// Applies to transfers submitted afterwards. Zero treats new and repeat
+    /// recipients alike.
+    pub fn update_new_recipient_approvals(env: Env, owner: Address, extra: u32) {
+        Self::require_owner(&env, owner.clone());
+
+        if extra > Self::get_authorized_admins(env.clone()).len() {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "new_recipient_extra_approvals"), &extra);
+
+        env.events().publish(
+            (Symbol::new(&env, "new_recipient_approvals_updated"), owner),
+            extra,
+        );
+    }
+
     /// Update maximum transfer amount (owner only)
+    ///
+    /// Decreases only tighten security and apply immediately. Increases are
+    /// scheduled and only take effect through `apply_max_transfer_increase`
+    /// once the timelock has passed.
     pub fn update_max_transfer_amount(env: Env, owner: Address, amount: i128) {
-        Self::require_owner(&env, owner);
-        env.storage().instance().set(&Symbol::new(&env, "max_transfer_amount"), &amount);
+        Self::require_owner(&env, owner.clone());
+
+        if amount <= 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        if amount <= Self::get_max_transfer_amount(env.clone()) {
+            env.storage().instance().set(&Symbol::new(&env, "max_transfer_amount"), &amount);
+            // A decrease supersedes any scheduled increase
+            env.storage().instance().remove(&Symbol::new(&env, "pending_limit_increase"));
+
+            env.events().publish(
+                (Symbol::new(&env, "max_transfer_amount_updated"), owner),
+                amount,
+            );
+            return;
+        }
+
+        let increase = PendingLimitIncrease {
+            amount,
+            effective_at: env.ledger().timestamp() + Self::get_limit_increase_delay(env.clone()),
+        };
+        env.storage().instance().set(&Symbol::new(&env, "pending_limit_increase"), &increase);
+
+        env.events().publish(
+            (Symbol::new(&env, "max_transfer_increase_scheduled"), owner),
+            (increase.amount, increase.effective_at),
+        );
+    }
+
+    /// Apply a scheduled max transfer amount increase after its timelock (owner only)
+    pub fn apply_max_transfer_increase(env: Env, owner: Address) {
+        Self::require_owner(&env, owner.clone());
+
+        let increase = Self::get_pending_limit_increase(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound));
+
+        if env.ledger().timestamp() < increase.effective_at {
+            panic_with_error!(&env, ContractError::InvalidState);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "max_transfer_amount"), &increase.amount);
+        env.storage().instance().remove(&Symbol::new(&env, "pending_limit_increase"));
+
+        env.events().publish(
+            (Symbol::new(&env, "max_transfer_amount_updated"), owner),
+            increase.amount,
+        );
+    }
+
+    /// Get the scheduled max transfer amount increase, if any
+    pub fn get_pending_limit_increase(env: Env) -> Option<PendingLimitIncrease> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "pending_limit_increase"))
+    }
+
+    /// Get the timelock applied to max transfer amount increases, in seconds
+    pub fn get_limit_increase_delay(env: Env) -> u64 {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "limit_increase_delay"))
+            .unwrap_or(172800)
+    }
+
+    /// Get the amount-based approval tier table
+    pub fn get_approval_tiers(env: Env) -> Vec<ApprovalTier> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "approval_tiers"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Update the amount-based approval tier table (owner only)
+    ///
+    /// Tiers must be sorted by strictly increasing `min_amount` and require at least one approval.
+    /// An empty table falls back to requiring every admin.
+    pub fn update_approval_tiers(env: Env, owner: Address, tiers: Vec<ApprovalTier>) {
+        Self::require_owner(&env, owner.clone());
+
+        let mut previous_min: Option<i128> = None;
+        for tier in tiers.iter() {
+            if tier.required_approvals == 0 {
+                panic_with_error!(&env, ContractError::InvalidInput);
+            }
+            if let Some(min) = previous_min {
+                if tier.min_amount <= min {
+                    panic_with_error!(&env, ContractError::InvalidInput);
+                }
+            }
+            previous_min = Some(tier.min_amount);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "approval_tiers"), &tiers);
+
+        env.events().publish((Symbol::new(&env, "approval_tiers_updated"), tiers.len()), ());
+    }
+
+    /// Update owner auto-approve limit (owner only)
+    pub fn update_owner_auto_approve_limit(env: Env, owner: Address, amount: i128) {
+        Self::require_owner(&env, owner.clone());
+
+        if amount < 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        Self::run_owner_action(&env, owner, OwnerAction::UpdateOwnerAutoApproveLimit(amount));
     }
 
     /// Update emergency cooldown period (owner only)
     pub fn update_emergency_cooldown(env: Env, owner: Address, cooldown_seconds: u64) {
-        Self::require_owner(&env, owner);
-        env.storage().instance().set(&Symbol::new(&env, "emergency_cooldown"), &cooldown_seconds);
+        Self::require_owner(&env, owner.clone());
+        Self::run_owner_action(&env, owner, OwnerAction::UpdateEmergencyCooldown(cooldown_seconds));
     }
 
     // Private helper methods
 
-    fn initialize(env: Env) {
-        // Set initial empty data
-        env.storage().instance().set(&Symbol::new(&env, "pending_transfers"), &Map::new(&env));
-        env.storage().instance().set(&Symbol::new(&env, "fund_allocation"), &FundAllocation::default());
-        env.storage().instance().set(&Symbol::new(&env, "max_transfer_amount"), &10000);
-        env.storage().instance().set(&Symbol::new(&env, "emergency_cooldown"), &3600);
+    fn initialize(env: &Env) {
+        // Set initial configuration
+        env.storage().instance().set(&Symbol::new(env, "fund_allocation"), &FundAllocation::default());
+        env.storage().instance().set(&Symbol::new(env, "max_transfer_amount"), &10000i128);
+        env.storage().instance().set(&Symbol::new(env, "owner_auto_approve_limit"), &10000i128);
+        env.storage().instance().set(&Symbol::new(env, "emergency_cooldown"), &3600u64);
+        env.storage().instance().set(&Symbol::new(env, "approval_validity_period"), &604800u64);
+        env.storage().instance().set(&Symbol::new(env, "force_confirmation_window"), &3600u64);
+        env.storage().instance().set(&Symbol::new(env, "emergency_limit_window"), &86400u64);
+    }
+
+    fn queue_owner_action(env: &Env, owner: Address, action: OwnerAction) -> u32 {
+        let action_id: u32 = env.storage().instance()
+            .get(&Symbol::new(env, "next_owner_action_id"))
+            .unwrap_or(0);
+        env.storage().instance().set(&Symbol::new(env, "next_owner_action_id"), &(action_id + 1));
+
+        let pending = PendingOwnerAction {
+            action,
+            proposed_at: env.ledger().timestamp(),
+        };
+        env.storage().instance().set(&(Symbol::new(env, "pending_owner_actions"), action_id), &pending);
+
+        env.events().publish(
+            (Symbol::new(env, "owner_action_pending"), action_id),
+            owner,
+        );
+
+        action_id
+    }
+
+    /// Apply an owner action, or queue it for an admin co-signature if the owner can't act alone
+    fn run_owner_action(env: &Env, owner: Address, action: OwnerAction) {
+        if Self::get_owner_cosign_required(env.clone()) {
+            Self::queue_owner_action(env, owner, action);
+            return;
+        }
+
+        Self::apply_owner_action(env, action);
+    }
+
+    fn apply_owner_action(env: &Env, action: OwnerAction) {
+        match action {
+            OwnerAction::UpdateFundAllocation(allocation) => {
+                env.storage().instance().set(&Symbol::new(env, "fund_allocation"), &allocation);
+
+                // Rebalance funds according to new allocation
+                let mut stats = Self::get_stats(env.clone());
+                stats.rebalance_funds(&allocation);
+                Self::set_stats(env, stats);
+
+                env.events().publish(
+                    (Symbol::new(env, "fund_allocation_updated"), allocation.insurance_percentage),
+                    (
+                        allocation.operational_percentage,
+                        allocation.emergency_percentage,
+                    ),
+                );
+            }
+            OwnerAction::EmergencyShutdown(reason) => {
+                env.storage().instance().set(&Symbol::new(env, "emergency_shutdown"), &true);
+
+                env.events().publish(
+                    (Symbol::new(env, "emergency_shutdown"), Self::get_owner(env.clone())),
+                    reason,
+                );
+            }
+            OwnerAction::DisableOwnerCosign => {
+                env.storage().instance().set(&Symbol::new(env, "owner_cosign_required"), &false);
+
+                env.events().publish(
+                    (Symbol::new(env, "owner_cosign_updated"), Self::get_owner(env.clone())),
+                    false,
+                );
+            }
+            OwnerAction::UpdateToken(token) => {
+                env.storage().instance().set(&Symbol::new(env, "token"), &Some(token));
+            }
+            OwnerAction::UpdateEmergencyDrawer(drawer) => {
+                env.storage().instance().set(&Symbol::new(env, "emergency_drawer"), &drawer);
+            }
+            OwnerAction::UpdateForceConfirmationWindow(window_seconds) => {
+                env.storage().instance().set(&Symbol::new(env, "force_confirmation_window"), &window_seconds);
+            }
+            OwnerAction::UpdateOwnerAutoApproveLimit(amount) => {
+                env.storage().instance().set(&Symbol::new(env, "owner_auto_approve_limit"), &amount);
+            }
+            OwnerAction::UpdateEmergencyCooldown(cooldown_seconds) => {
+                env.storage().instance().set(&Symbol::new(env, "emergency_cooldown"), &cooldown_seconds);
+            }
+        }
     }
 
     fn require_owner(env: &Env, caller: Address) {
-        let owner = Self::get_owner(env);
+        let owner = Self::get_owner(env.clone());
         if caller != owner {
             panic_with_error!(env, ContractError::Unauthorized);
         }
+        caller.require_auth();
     }
 
     fn require_admin(env: &Env, caller: Address) {
-        let admins = Self::get_authorized_admins(env);
+        let admins = Self::get_authorized_admins(env.clone());
         if !admins.contains(&caller) {
             panic_with_error!(env, ContractError::Unauthorized);
         }
+        caller.require_auth();
+    }
+
+    fn get_delegations(env: &Env) -> Map<Address, ApprovalDelegation> {
+        env.storage().instance()
+            .get(&Symbol::new(env, "approval_delegations"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Map an approver to the administrator whose approval they cast
+    fn resolve_approver(env: &Env, approver: Address) -> Address {
+        let admins = Self::get_authorized_admins(env.clone());
+        if admins.contains(&approver) {
+            return approver;
+        }
+
+        for (admin, delegation) in Self::get_delegations(env).iter() {
+            if delegation.delegate == approver && delegation.is_active(env) && admins.contains(&admin) {
+                return admin;
+            }
+        }
+
+        panic_with_error!(env, ContractError::Unauthorized);
     }
 
     fn validate_transfer_params(env: &Env, params: &TransferParams) {
@@ -508,38 +1621,199 @@ impl Treasury {
             panic!("Transfer amount must be positive");
         }
 
-        if params.amount > Self::get_max_transfer_amount(env) && !params.is_emergency {
+        if params.amount > Self::get_max_transfer_amount(env.clone()) && !params.is_emergency {
             panic!("Transfer amount exceeds maximum limit for non-emergency transfers");
         }
 
+        // Reject dust transfers that would only clog the approval queue
+        if params.amount < Self::get_min_transfer_amount(env.clone()) {
+            panic_with_error!(env, ContractError::InvalidInput);
+        }
+
         // Validate that recipient address is not zero
-        if params.to == Address::zero(env) {
-            panic!("Invalid recipient address");
+        require_nonzero_address(env, &params.to);
+
+        // Asset transfers must use a supported asset
+        if let Some(asset) = &params.asset {
+            if !Self::get_assets(env.clone()).contains(asset) {
+                panic_with_error!(env, ContractError::InvalidInput);
+            }
         }
     }
 
     fn get_default_required_approvals(env: &Env, params: &TransferParams) -> u32 {
-        let admins = Self::get_authorized_admins(env);
+        let admins = Self::get_authorized_admins(env.clone());
+
+        // Use the tier matching the amount, otherwise all admins must approve
+        let required = Self::get_tiered_required_approvals(env, params.amount)
+            .unwrap_or(admins.len());
 
         // Emergency transfers need fewer approvals
         if params.is_emergency {
-            (admins.len() + 1) / 2
+            required.div_ceil(2)
         } else {
-            admins.len() // All admins must approve for regular transfers
+            required
         }
     }
 
-    fn get_treasury_balance(env: Env) -> i128 {
-        let stats = Self::get_stats(env);
+    fn get_tiered_required_approvals(env: &Env, amount: i128) -> Option<u32> {
+        let mut required = None;
+        for tier in Self::get_approval_tiers(env.clone()).iter() {
+            if amount >= tier.min_amount {
+                required = Some(tier.required_approvals);
+            }
+        }
+        required
+    }
+
+    fn get_treasury_balance(env: &Env) -> i128 {
+        let stats = Self::get_stats(env.clone());
         stats.total_balance
     }
 
+    fn execution_blocker(env: &Env, transfer: &PendingTransfer, skip_cooldown: bool) -> Option<&'static str> {
+        let validity_period = Self::get_approval_validity_period(env.clone());
+
+        if !transfer.can_be_executed(env, validity_period) {
+            // Enough approvals were given but some have since expired
+            if transfer.approvals >= transfer.required_approvals {
+                return Some("expired");
+            }
+            return Some("insufficient_approvals");
+        }
+
+        // Only a confirmed force-execution may skip the cooldown
+        if !skip_cooldown && !Self::is_cooldown_elapsed(env, transfer) {
+            return Some("in_cooldown");
+        }
+
+        let available_balance = match &transfer.asset {
+            Some(asset) => Self::get_stats(env.clone()).asset_balance(asset),
+            None => Self::get_treasury_balance(env),
+        };
+        if available_balance < transfer.amount {
+            return Some("insufficient_balance");
+        }
+
+        if transfer.is_emergency_transfer() && Self::exceeds_emergency_limit(env, transfer.amount) {
+            return Some("emergency_limit");
+        }
+
+        None
+    }
+
+    fn exceeds_emergency_limit(env: &Env, amount: i128) -> bool {
+        let limit = Self::get_emergency_limit(env.clone());
+        limit > 0 && Self::get_emergency_spent(env.clone()) + amount > limit
+    }
+
+    fn record_emergency_spend(env: &Env, amount: i128) {
+        let key = Symbol::new(env, "emergency_spent");
+        let now = env.ledger().timestamp();
+        let (window_start, spent): (u64, i128) = env.storage().instance().get(&key).unwrap_or((0, 0));
+
+        // Start a new window once the current one has elapsed
+        let entry = if now - window_start >= Self::get_emergency_limit_window(env.clone()) {
+            (now, amount)
+        } else {
+            (window_start, spent + amount)
+        };
+        env.storage().instance().set(&key, &entry);
+    }
+
+    fn is_cooldown_elapsed(env: &Env, transfer: &PendingTransfer) -> bool {
+        // Emergency transfers get no exemption here; they skip the cooldown
+        // only through a confirmed `force_execute`
+        transfer.age(env) >= Self::get_emergency_cooldown(env.clone())
+    }
+
+    fn get_force_confirmations(env: &Env, transfer_id: &Bytes) -> Map<Address, u64> {
+        env.storage().instance()
+            .get(&(Symbol::new(env, "force_confirmations"), transfer_id.clone()))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn execute_transfer_internal(env: &Env, admin: &Address, transfer_id: &Bytes, skip_cooldown: bool) {
+        // Always re-load the transfer and statistics so callers can't pass stale state
+        let mut transfer = Self::get_pending_transfer(env.clone(), transfer_id.clone());
+
+        match Self::execution_blocker(env, &transfer, skip_cooldown) {
+            Some("in_cooldown") => panic!("Transfer is within cooldown period"),
+            Some("insufficient_balance") => panic_with_error!(env, ContractError::InsufficientBalance),
+            Some("emergency_limit") => panic_with_error!(env, ContractError::EmergencyLimitExceeded),
+            Some(_) => panic_with_error!(env, ContractError::TransferNotAuthorized),
+            None => {}
+        }
+
+        let mut stats = Self::get_stats(env.clone());
+
+        // Execute the transfer
+        // Internal-balance transfers are bookkeeping only; in production they would move funds too
+        if let Some(asset) = &transfer.asset {
+            token::Client::new(env, asset)
+                .transfer(&env.current_contract_address(), &transfer.to, &transfer.amount);
+            stats.remove_asset_funds(asset, transfer.amount);
+        }
+        transfer.mark_as_executed(env);
+
+        if transfer.is_emergency_transfer() {
+            Self::record_emergency_spend(env, transfer.amount);
+        }
+
+        // Later transfers to this recipient fall back to the normal threshold
+        env.storage().instance().set(&(Symbol::new(env, "paid_recipients"), transfer.to.clone()), &true);
+
+        // Update statistics
+        stats.decrement_pending_transfers();
+        stats.increment_executed_transfers();
+        stats.transfer_funds("treasury", "external", transfer.amount);
+        stats.record_category_transfer(transfer.category, transfer.amount);
+        Self::set_stats(env, stats);
+
+        // Remove from pending transfers
+        Self::remove_pending_transfer(env, transfer_id);
+
+        // Emit event
+        env.events().publish(
+            (Symbol::new(env, "transfer_executed"), transfer_id.clone()),
+            (
+                transfer.to,
+                transfer.amount,
+                transfer.category,
+                transfer.asset,
+                admin.clone(),
+            ),
+        );
+    }
+
     fn set_stats(env: &Env, stats: TreasuryStats) {
-        env.storage().instance().set(&Symbol::new(&env, "stats"), &stats);
+        env.storage().instance().set(&Symbol::new(env, "stats"), &stats);
+    }
+
+    fn get_transfer_submitter(env: &Env, transfer_id: &Bytes) -> Address {
+        env.storage().instance()
+            .get(&(Symbol::new(env, "transfer_submitters"), transfer_id.clone()))
+            .unwrap_or_else(|| Self::get_owner(env.clone()))
     }
 
-    fn get_transfer_submitter(env: &Env, transfer: &PendingTransfer) -> Address {
-        // Simplified - in production, track who submitted each transfer
-        Self::get_owner(env)
+    fn store_pending_transfer(env: &Env, transfer_id: &Bytes, transfer: &PendingTransfer) {
+        let key = (Symbol::new(env, "pending_transfers"), transfer_id.clone());
+        if !env.storage().instance().has(&key) {
+            let mut transfer_ids = Self::get_all_pending_transfers(env.clone());
+            transfer_ids.push_back(transfer_id.clone());
+            env.storage().instance().set(&Symbol::new(env, "pending_transfer_ids"), &transfer_ids);
+        }
+        env.storage().instance().set(&key, transfer);
+    }
+
+    fn remove_pending_transfer(env: &Env, transfer_id: &Bytes) {
+        env.storage().instance().remove(&(Symbol::new(env, "pending_transfers"), transfer_id.clone()));
+        env.storage().instance().remove(&(Symbol::new(env, "transfer_submitters"), transfer_id.clone()));
+
+        let mut transfer_ids = Self::get_all_pending_transfers(env.clone());
+        if let Some(index) = transfer_ids.first_index_of(transfer_id) {
+            transfer_ids.remove(index);
+        }
+        env.storage().instance().set(&Symbol::new(env, "pending_transfer_ids"), &transfer_ids);
     }
 }
\ No newline at end of file
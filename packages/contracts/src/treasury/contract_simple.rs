@@ -135,7 +135,7 @@ impl Treasury {
 
         // Get the pending transfer
         let mut transfer = contract.pending_transfers.get(transfer_id.clone())
-            .unwrap_or_else(|| panic!("Transfer not found"));
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound));
 
         // Check if admin has already approved
         if transfer.approvers.contains(&caller) {
@@ -162,7 +162,7 @@ impl Treasury {
     /// Execute an approved transfer (internal function)
     fn execute_transfer_internal(env: Env, transfer_id: Bytes, contract: &mut Treasury) {
         let transfer = contract.pending_transfers.get(transfer_id.clone())
-            .unwrap_or_else(|| panic!("Transfer not found"));
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound));
 
         if transfer.approvals < transfer.required_approvals {
             panic_with_error!(&env, ContractError::TransferNotAuthorized);
@@ -191,7 +191,7 @@ impl Treasury {
     /// Add funds to the treasury
     pub fn add_funds(env: Env, from: Address, amount: i128) {
         if amount <= 0 {
-            panic!("Amount must be positive");
+            panic_with_error!(&env, ContractError::InvalidInput);
         }
 
         let mut contract = env.storage().instance()
@@ -213,7 +213,7 @@ impl Treasury {
             .unwrap_or_else(|| panic!("Contract not initialized"));
 
         contract.pending_transfers.get(transfer_id)
-            .unwrap_or_else(|| panic!("Transfer not found"))
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound))
     }
 
     /// Get all pending transfers
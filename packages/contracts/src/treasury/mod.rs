@@ -3,5 +3,5 @@
 pub mod contract;
 pub mod types;
 
-pub use contract::Treasury;
-pub use types::{PendingTransfer, TransferStatus, TreasuryStats};
\ No newline at end of file
+pub use contract::{Treasury, TreasuryClient};
+pub use types::{AdminInfo, ApprovalDelegation, ContractInfo, ApprovalTier, FundAllocation, OwnerAction, PendingLimitIncrease, PendingOwnerAction, PendingTransfer, TransferParams, TransferStatus, TransferCategory, CategoryTotals, TreasuryStats};
\ No newline at end of file
@@ -1,18 +1,25 @@
 //! Treasury contract types
 
-use soroban_sdk::{Address, Env, Symbol, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, Env, Map, String, Symbol, Vec};
+
+use crate::shared::{saturating_u32, split_by_percentage};
 
 /// Pending transfer requiring multi-signature approval
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct PendingTransfer {
     /// Unique transfer identifier
-    pub transfer_id: Vec<u8>,
+    pub transfer_id: Bytes,
     /// Recipient address
     pub to: Address,
     /// Transfer amount
     pub amount: i128,
     /// Reason for the transfer
     pub reason: Symbol,
+    /// Spending category of the transfer
+    pub category: TransferCategory,
+    /// Asset to move (`None` for the treasury's internal balance)
+    pub asset: Option<Address>,
     /// Number of approvals received
     pub approvals: u32,
     /// Number of approvals required
@@ -25,12 +32,15 @@ pub struct PendingTransfer {
     pub status: TransferStatus,
     /// Addresses that have approved the transfer
     pub approvers: Vec<Address>,
+    /// Approval timestamps, parallel to `approvers`
+    pub approval_times: Vec<u64>,
     /// Whether this is an emergency transfer
     pub is_emergency: bool,
 }
 
 /// Transfer status
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
 pub enum TransferStatus {
     /// Transfer is pending approvals
     Pending,
@@ -44,10 +54,45 @@ pub enum TransferStatus {
     Cancelled,
     /// Transfer has failed
     Failed,
+    /// Transfer is contested and frozen until the owner resolves the dispute
+    Disputed,
+}
+
+/// Spending category used to group transfers for reporting
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TransferCategory {
+    /// Salaries and contributor compensation
+    Payroll,
+    /// Ecosystem or community grants
+    Grant,
+    /// Insurance fund movements and claim payouts
+    Insurance,
+    /// Operational expenses
+    Ops,
+    /// Anything not covered above
+    Other,
+}
+
+/// Cumulative executed amounts per transfer category
+#[derive(Clone, Debug, Default)]
+#[contracttype]
+pub struct CategoryTotals {
+    /// Total transferred for payroll
+    pub payroll: i128,
+    /// Total transferred for grants
+    pub grant: i128,
+    /// Total transferred for insurance
+    pub insurance: i128,
+    /// Total transferred for operations
+    pub ops: i128,
+    /// Total transferred for other purposes
+    pub other: i128,
 }
 
 /// Treasury statistics
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct TreasuryStats {
     /// Total balance in treasury
     pub total_balance: i128,
@@ -63,10 +108,17 @@ pub struct TreasuryStats {
     pub total_transferred: i128,
     /// Emergency fund balance
     pub emergency_fund_balance: i128,
+    /// Executed amounts grouped by category
+    pub category_totals: CategoryTotals,
+    /// Protocol fee revenue held separately from user-owed funds
+    pub protocol_fees_collected: i128,
+    /// Balances held per supported asset (including the native XLM SAC)
+    pub asset_balances: Map<Address, i128>,
 }
 
 /// Transfer parameters
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct TransferParams {
     /// Recipient address
     pub to: Address,
@@ -74,14 +126,125 @@ pub struct TransferParams {
     pub amount: i128,
     /// Reason for the transfer
     pub reason: Symbol,
+    /// Spending category of the transfer
+    pub category: TransferCategory,
+    /// Asset to move (`None` for the treasury's internal balance)
+    pub asset: Option<Address>,
     /// Number of approvals required (uses default if None)
     pub required_approvals: Option<u32>,
     /// Whether this is an emergency transfer
     pub is_emergency: bool,
 }
 
+/// Approval requirement for transfers at or above an amount
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ApprovalTier {
+    /// Smallest transfer amount this tier applies to
+    pub min_amount: i128,
+    /// Number of approvals required within this tier
+    pub required_approvals: u32,
+}
+
+/// Scheduled increase of the maximum transfer amount
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct PendingLimitIncrease {
+    /// New maximum transfer amount
+    pub amount: i128,
+    /// Timestamp after which the increase can be applied
+    pub effective_at: u64,
+}
+
+/// Owner-only action that needs an administrator co-signature
+#[derive(Clone, Debug)]
+#[contracttype]
+pub enum OwnerAction {
+    /// Replace the fund allocation
+    UpdateFundAllocation(FundAllocation),
+    /// Enable emergency shutdown with the given reason
+    EmergencyShutdown(Symbol),
+    /// Turn off the co-signer requirement
+    DisableOwnerCosign,
+    /// Replace the token the treasury holds
+    UpdateToken(Address),
+    /// Set or clear the contract allowed to draw from the emergency fund
+    UpdateEmergencyDrawer(Option<Address>),
+    /// Replace the force-execution confirmation window, in seconds
+    UpdateForceConfirmationWindow(u64),
+    /// Replace the owner auto-approve limit
+    UpdateOwnerAutoApproveLimit(i128),
+    /// Replace the emergency cooldown period, in seconds
+    UpdateEmergencyCooldown(u64),
+}
+
+/// Owner action queued until an administrator co-signs it
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct PendingOwnerAction {
+    /// Action to apply once co-signed
+    pub action: OwnerAction,
+    /// Timestamp the owner proposed the action
+    pub proposed_at: u64,
+}
+
+impl PendingOwnerAction {
+    /// Check if the co-signing window has passed
+    pub fn is_expired(&self, env: &Env, window: u64) -> bool {
+        env.ledger().timestamp() - self.proposed_at > window
+    }
+}
+
+/// Administrator details for governance views
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct AdminInfo {
+    /// Administrator address
+    pub address: Address,
+    /// Role bitmask (see `shared::Role`)
+    pub roles: u32,
+    /// Voting weight of the administrator's approvals
+    pub weight: u32,
+}
+
+/// Deployed version and headline configuration of the treasury contract
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ContractInfo {
+    /// Semantic version of the contract
+    pub version: String,
+    /// Treasury owner
+    pub owner: Address,
+    /// Number of authorized administrators
+    pub admin_count: u32,
+    /// Maximum amount of a single non-emergency transfer
+    pub max_transfer_amount: i128,
+    /// Cooldown before emergency transfers can execute, in seconds
+    pub emergency_cooldown: u64,
+    /// Whether emergency shutdown is active
+    pub emergency_shutdown: bool,
+}
+
+/// Time-bounded delegation of an administrator's approval authority
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ApprovalDelegation {
+    /// Address approving on the administrator's behalf
+    pub delegate: Address,
+    /// Timestamp after which the delegation no longer applies
+    pub until: u64,
+}
+
+impl ApprovalDelegation {
+    /// Check if the delegation is still in effect
+    pub fn is_active(&self, env: &Env) -> bool {
+        env.ledger().timestamp() < self.until
+    }
+}
+
 /// Fund allocation parameters
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct FundAllocation {
     /// Percentage allocated to insurance fund (0-100)
     pub insurance_percentage: u32,
@@ -104,7 +267,7 @@ impl Default for FundAllocation {
 impl PendingTransfer {
     /// Create a new pending transfer
     pub fn new(
-        transfer_id: Vec<u8>,
+        transfer_id: Bytes,
         params: TransferParams,
         required_approvals: u32,
         env: &Env,
@@ -114,36 +277,59 @@ impl PendingTransfer {
             to: params.to,
             amount: params.amount,
             reason: params.reason,
+            category: params.category,
+            asset: params.asset,
             approvals: 0,
             required_approvals,
             created_at: env.ledger().timestamp(),
             executed_at: None,
             status: TransferStatus::Pending,
             approvers: Vec::new(env),
+            approval_times: Vec::new(env),
             is_emergency: params.is_emergency,
         }
     }
 
-    /// Add an approval to the transfer
-    pub fn add_approval(&mut self, approver: Address) {
-        if !self.approvers.contains(&approver) {
-            self.approvers.push_back(approver);
-            self.approvals += 1;
+    /// Add an approval to the transfer, refreshing the timestamp of an existing one
+    pub fn add_approval(&mut self, approver: Address, env: &Env) {
+        let now = env.ledger().timestamp();
+        match self.approvers.first_index_of(&approver) {
+            Some(index) => self.approval_times.set(index, now),
+            None => {
+                self.approvers.push_back(approver);
+                self.approval_times.push_back(now);
+                self.approvals += 1;
+            }
         }
     }
 
-    /// Check if the transfer has sufficient approvals
-    pub fn has_sufficient_approvals(&self) -> bool {
-        self.approvals >= self.required_approvals
+    /// Count approvals given within the last `validity_period` seconds
+    pub fn valid_approvals(&self, env: &Env, validity_period: u64) -> u32 {
+        let now = env.ledger().timestamp();
+        let mut count = 0;
+        for approved_at in self.approval_times.iter() {
+            if now - approved_at < validity_period {
+                count += 1;
+            }
+        }
+        count
     }
 
-    /// Check if an address has already approved the transfer
-    pub fn has_approved(&self, approver: &Address) -> bool {
-        self.approvers.contains(approver)
+    /// Check if the transfer has sufficient unexpired approvals
+    pub fn has_sufficient_approvals(&self, env: &Env, validity_period: u64) -> bool {
+        self.valid_approvals(env, validity_period) >= self.required_approvals
+    }
+
+    /// Check if an address has an unexpired approval on the transfer
+    pub fn has_approved(&self, approver: &Address, env: &Env, validity_period: u64) -> bool {
+        match self.approvers.first_index_of(approver) {
+            Some(index) => env.ledger().timestamp() - self.approval_times.get(index).unwrap() < validity_period,
+            None => false,
+        }
     }
 
     /// Mark the transfer as approved
-    pub fn mark_as_approved(&mut self, env: &Env) {
+    pub fn mark_as_approved(&mut self, _env: &Env) {
         self.status = TransferStatus::Approved;
     }
 
@@ -154,17 +340,27 @@ impl PendingTransfer {
     }
 
     /// Mark the transfer as rejected
-    pub fn mark_as_rejected(&mut self, env: &Env) {
+    pub fn mark_as_rejected(&mut self, _env: &Env) {
         self.status = TransferStatus::Rejected;
     }
 
+    /// Freeze the transfer while a dispute is open
+    pub fn mark_as_disputed(&mut self, _env: &Env) {
+        self.status = TransferStatus::Disputed;
+    }
+
+    /// Check if the transfer is under dispute
+    pub fn is_disputed(&self) -> bool {
+        matches!(self.status, TransferStatus::Disputed)
+    }
+
     /// Cancel the transfer
-    pub fn cancel(&mut self, env: &Env) {
+    pub fn cancel(&mut self, _env: &Env) {
         self.status = TransferStatus::Cancelled;
     }
 
     /// Mark the transfer as failed
-    pub fn mark_as_failed(&mut self, env: &Env) {
+    pub fn mark_as_failed(&mut self, _env: &Env) {
         self.status = TransferStatus::Failed;
     }
 
@@ -174,8 +370,8 @@ impl PendingTransfer {
     }
 
     /// Check if the transfer can be executed
-    pub fn can_be_executed(&self) -> bool {
-        matches!(self.status, TransferStatus::Approved) && self.has_sufficient_approvals()
+    pub fn can_be_executed(&self, env: &Env, validity_period: u64) -> bool {
+        matches!(self.status, TransferStatus::Approved) && self.has_sufficient_approvals(env, validity_period)
     }
 
     /// Get the age of the transfer in seconds
@@ -191,7 +387,7 @@ impl PendingTransfer {
 
 impl TreasuryStats {
     /// Create new pool statistics
-    pub fn new() -> Self {
+    pub fn new(env: &Env) -> Self {
         Self {
             total_balance: 0,
             insurance_fund_balance: 0,
@@ -200,6 +396,9 @@ impl TreasuryStats {
             executed_transfers: 0,
             total_transferred: 0,
             emergency_fund_balance: 0,
+            category_totals: CategoryTotals::default(),
+            protocol_fees_collected: 0,
+            asset_balances: Map::new(env),
         }
     }
 
@@ -213,13 +412,68 @@ impl TreasuryStats {
         self.total_balance = self.total_balance.saturating_sub(amount);
     }
 
+    /// Get the balance held in a specific asset
+    pub fn asset_balance(&self, asset: &Address) -> i128 {
+        self.asset_balances.get(asset.clone()).unwrap_or(0)
+    }
+
+    /// Add funds held in a specific asset
+    pub fn add_asset_funds(&mut self, asset: &Address, amount: i128) {
+        let balance = self.asset_balance(asset);
+        self.asset_balances.set(asset.clone(), balance + amount);
+    }
+
+    /// Remove funds held in a specific asset
+    pub fn remove_asset_funds(&mut self, asset: &Address, amount: i128) {
+        let balance = self.asset_balance(asset);
+        self.asset_balances.set(asset.clone(), balance.saturating_sub(amount));
+    }
+
+    /// Record protocol fee revenue
+    pub fn add_protocol_fees(&mut self, amount: i128) {
+        self.total_balance += amount;
+        self.protocol_fees_collected += amount;
+    }
+
+    /// Remove claimed protocol fees
+    pub fn remove_protocol_fees(&mut self, amount: i128) {
+        self.protocol_fees_collected -= amount;
+        self.total_balance -= amount;
+    }
+
+    /// Get the balance excluding protocol fee revenue
+    pub fn principal_balance(&self) -> i128 {
+        self.total_balance - self.protocol_fees_collected
+    }
+
+    /// Zero every balance after the treasury has been swept
+    pub fn clear_balances(&mut self, env: &Env) {
+        self.total_balance = 0;
+        self.insurance_fund_balance = 0;
+        self.operational_fund_balance = 0;
+        self.emergency_fund_balance = 0;
+        self.protocol_fees_collected = 0;
+        self.asset_balances = Map::new(env);
+    }
+
+    /// Draw from the emergency fund, reducing the total balance
+    pub fn remove_emergency_funds(&mut self, amount: i128) {
+        self.emergency_fund_balance -= amount;
+        self.total_balance -= amount;
+    }
+
     /// Transfer funds between accounts
-    pub fn transfer_funds(&mut self, from_account: &str, to_account: &str, amount: i128) {
+    pub fn transfer_funds(&mut self, _from_account: &str, _to_account: &str, amount: i128) {
         // Simplified fund transfer tracking
         // In production, this would use proper accounting
         self.total_transferred += amount;
     }
 
+    /// Record an executed transfer against its category
+    pub fn record_category_transfer(&mut self, category: TransferCategory, amount: i128) {
+        self.category_totals.add(category, amount);
+    }
+
     /// Increment pending transfers
     pub fn increment_pending_transfers(&mut self) {
         self.pending_transfers += 1;
@@ -242,7 +496,7 @@ impl TreasuryStats {
         if self.total_balance == 0 {
             return 0;
         }
-        ((self.insurance_fund_balance * 100) / self.total_balance) as u32
+        saturating_u32(self.insurance_fund_balance.saturating_mul(100) / self.total_balance)
     }
 
     /// Get percentage of funds in operational fund
@@ -250,7 +504,7 @@ impl TreasuryStats {
         if self.total_balance == 0 {
             return 0;
         }
-        ((self.operational_fund_balance * 100) / self.total_balance) as u32
+        saturating_u32(self.operational_fund_balance.saturating_mul(100) / self.total_balance)
     }
 
     /// Get percentage of funds in emergency fund
@@ -258,15 +512,19 @@ impl TreasuryStats {
         if self.total_balance == 0 {
             return 0;
         }
-        ((self.emergency_fund_balance * 100) / self.total_balance) as u32
+        saturating_u32(self.emergency_fund_balance.saturating_mul(100) / self.total_balance)
     }
 
     /// Rebalance funds according to allocation percentages
+    ///
+    /// The rounding remainder goes to the emergency fund, so the three funds
+    /// always sum to the principal balance exactly.
     pub fn rebalance_funds(&mut self, allocation: &FundAllocation) {
-        // Calculate target amounts
-        let target_insurance = (self.total_balance * allocation.insurance_percentage as i128) / 100;
-        let target_operational = (self.total_balance * allocation.operational_percentage as i128) / 100;
-        let target_emergency = (self.total_balance * allocation.emergency_percentage as i128) / 100;
+        // Calculate target amounts (protocol fees are not allocated)
+        let principal = self.principal_balance();
+        let (target_insurance, rest) = split_by_percentage(principal, allocation.insurance_percentage);
+        let target_operational = split_by_percentage(principal, allocation.operational_percentage).0;
+        let target_emergency = rest - target_operational;
 
         // Update balances (simplified - would need proper fund movement logic)
         self.insurance_fund_balance = target_insurance;
@@ -275,8 +533,26 @@ impl TreasuryStats {
     }
 }
 
-impl Default for TreasuryStats {
-    fn default() -> Self {
-        Self::new()
+impl CategoryTotals {
+    /// Add an amount to the total for a category
+    pub fn add(&mut self, category: TransferCategory, amount: i128) {
+        match category {
+            TransferCategory::Payroll => self.payroll += amount,
+            TransferCategory::Grant => self.grant += amount,
+            TransferCategory::Insurance => self.insurance += amount,
+            TransferCategory::Ops => self.ops += amount,
+            TransferCategory::Other => self.other += amount,
+        }
     }
-}
\ No newline at end of file
+
+    /// Get the total for a category
+    pub fn get(&self, category: TransferCategory) -> i128 {
+        match category {
+            TransferCategory::Payroll => self.payroll,
+            TransferCategory::Grant => self.grant,
+            TransferCategory::Insurance => self.insurance,
+            TransferCategory::Ops => self.ops,
+            TransferCategory::Other => self.other,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! Simple Treasury Contract (No Constructor Version)
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, Symbol, Vec, Bytes, String};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, Symbol, Vec, Bytes, panic_with_error};
+
+use crate::shared::{generate_id, next_id_nonce, ContractError};
 
 /// Transfer status
 #[derive(Clone, Debug, PartialEq, Copy)]
@@ -74,14 +76,11 @@ impl Treasury {
         amount: i128,
         memo: Symbol,
     ) -> Bytes {
-        // Generate transfer ID
-        let sequence = env.ledger().sequence();
-        let transfer_id = Bytes::from_array(&env, &[
-            (sequence & 0xFF) as u8,
-            ((sequence >> 8) & 0xFF) as u8,
-            ((sequence >> 16) & 0xFF) as u8,
-            ((sequence >> 24) & 0xFF) as u8,
-        ]);
+        if amount <= 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let transfer_id = generate_id(&env, Symbol::new(&env, "transfer"), &from_address, next_id_nonce(&env));
 
         let transfer = TransferRequest {
             transfer_id: transfer_id.clone(),
@@ -98,9 +97,23 @@ impl Treasury {
             .get(&Symbol::new(&env, "transfers"))
             .unwrap_or(Map::new(&env));
 
-        transfers.set(transfer_id.clone(), transfer);
+        transfers.set(transfer_id.clone(), transfer.clone());
         env.storage().instance().set(&Symbol::new(&env, "transfers"), &transfers);
 
+        // Update indexes so getters don't have to scan every transfer
+        let mut pending_ids: Vec<Bytes> = env.storage().instance()
+            .get(&Symbol::new(&env, "pending_ids"))
+            .unwrap_or(Vec::new(&env));
+        if !pending_ids.contains(&transfer_id) {
+            pending_ids.push_back(transfer_id.clone());
+        }
+        env.storage().instance().set(&Symbol::new(&env, "pending_ids"), &pending_ids);
+
+        Self::index_user_transfer(&env, &transfer.from_address, &transfer_id);
+        if transfer.to_address != transfer.from_address {
+            Self::index_user_transfer(&env, &transfer.to_address, &transfer_id);
+        }
+
         // Update stats
         let mut stats: TreasuryStats = env.storage().instance()
             .get(&Symbol::new(&env, "stats"))
@@ -125,8 +138,9 @@ impl Treasury {
 
         if let Some(mut transfer) = transfers.get(transfer_id.clone()) {
             transfer.status = TransferStatus::Approved;
-            transfers.set(transfer_id, transfer);
+            transfers.set(transfer_id.clone(), transfer);
             env.storage().instance().set(&Symbol::new(&env, "transfers"), &transfers);
+            Self::remove_pending_id(&env, &transfer_id);
 
             // Update stats
             let mut stats: TreasuryStats = env.storage().instance()
@@ -155,8 +169,9 @@ impl Treasury {
 
         if let Some(mut transfer) = transfers.get(transfer_id.clone()) {
             transfer.status = TransferStatus::Rejected;
-            transfers.set(transfer_id, transfer);
+            transfers.set(transfer_id.clone(), transfer);
             env.storage().instance().set(&Symbol::new(&env, "transfers"), &transfers);
+            Self::remove_pending_id(&env, &transfer_id);
 
             // Update stats
             let mut stats: TreasuryStats = env.storage().instance()
@@ -217,34 +232,29 @@ impl Treasury {
             .get(&Symbol::new(&env, "transfers"))
             .unwrap_or(Map::new(&env));
 
-        transfers.get(transfer_id).unwrap_or_else(|| {
-            TransferRequest {
-                transfer_id: Bytes::from_array(&env, &[0; 4]),
-                from_address: Address::from_string(&String::from_str(&env, "GDQD3UOVCPUTS32XS37N6BJGWAXCARWH7YIDTZUAWMHQEGBXIM3HQ66YV")),
-                to_address: Address::from_string(&String::from_str(&env, "GDQD3UOVCPUTS32XS37N6BJGWAXCARWH7YIDTZUAWMHQEGBXIM3HQ66YV")),
-                amount: 0,
-                status: TransferStatus::Pending,
-                created_at: 0,
-                memo: Symbol::new(&env, "not_found"),
-            }
-        })
+        transfers.get(transfer_id).unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound))
     }
 
     /// Get all transfers for a user
     pub fn get_user_transfers(env: Env, user: Address, status: Option<TransferStatus>) -> Vec<Bytes> {
+        let user_index: Map<Address, Vec<Bytes>> = env.storage().instance()
+            .get(&Symbol::new(&env, "user_transfers"))
+            .unwrap_or(Map::new(&env));
+        let transfer_ids = user_index.get(user).unwrap_or(Vec::new(&env));
+
+        let filter_status = match status {
+            Some(filter_status) => filter_status,
+            None => return transfer_ids,
+        };
+
         let transfers: Map<Bytes, TransferRequest> = env.storage().instance()
             .get(&Symbol::new(&env, "transfers"))
             .unwrap_or(Map::new(&env));
 
         let mut user_transfers = Vec::new(&env);
-
-        for (transfer_id, transfer) in transfers {
-            if transfer.from_address == user || transfer.to_address == user {
-                if let Some(filter_status) = status {
-                    if transfer.status == filter_status {
-                        user_transfers.push_back(transfer_id);
-                    }
-                } else {
+        for transfer_id in transfer_ids.iter() {
+            if let Some(transfer) = transfers.get(transfer_id.clone()) {
+                if transfer.status == filter_status {
                     user_transfers.push_back(transfer_id);
                 }
             }
@@ -255,19 +265,9 @@ impl Treasury {
 
     /// Get all pending transfers
     pub fn get_pending_transfers(env: Env) -> Vec<Bytes> {
-        let transfers: Map<Bytes, TransferRequest> = env.storage().instance()
-            .get(&Symbol::new(&env, "transfers"))
-            .unwrap_or(Map::new(&env));
-
-        let mut pending = Vec::new(&env);
-
-        for (transfer_id, transfer) in transfers {
-            if transfer.status == TransferStatus::Pending {
-                pending.push_back(transfer_id);
-            }
-        }
-
-        pending
+        env.storage().instance()
+            .get(&Symbol::new(&env, "pending_ids"))
+            .unwrap_or(Vec::new(&env))
     }
 
     /// Get treasury statistics
@@ -320,6 +320,10 @@ impl Treasury {
 
     /// Add funds to treasury
     pub fn add_funds(env: Env, amount: i128) {
+        if amount <= 0 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
         let mut stats: TreasuryStats = env.storage().instance()
             .get(&Symbol::new(&env, "stats"))
             .unwrap_or(TreasuryStats {
@@ -341,4 +345,30 @@ impl Treasury {
 
         transfers.contains_key(transfer_id)
     }
+
+    fn index_user_transfer(env: &Env, user: &Address, transfer_id: &Bytes) {
+        let mut user_index: Map<Address, Vec<Bytes>> = env.storage().instance()
+            .get(&Symbol::new(env, "user_transfers"))
+            .unwrap_or(Map::new(env));
+
+        let mut transfer_ids = user_index.get(user.clone()).unwrap_or(Vec::new(env));
+        if !transfer_ids.contains(transfer_id) {
+            transfer_ids.push_back(transfer_id.clone());
+        }
+
+        user_index.set(user.clone(), transfer_ids);
+        env.storage().instance().set(&Symbol::new(env, "user_transfers"), &user_index);
+    }
+
+    fn remove_pending_id(env: &Env, transfer_id: &Bytes) {
+        let mut pending_ids: Vec<Bytes> = env.storage().instance()
+            .get(&Symbol::new(env, "pending_ids"))
+            .unwrap_or(Vec::new(env));
+
+        if let Some(index) = pending_ids.first_index_of(transfer_id) {
+            pending_ids.remove(index);
+        }
+
+        env.storage().instance().set(&Symbol::new(env, "pending_ids"), &pending_ids);
+    }
 }
\ No newline at end of file
@@ -124,7 +124,7 @@ impl YieldAggregator {
 
         // Get deposit
         let mut deposit = contract.deposit_data.get(deposit_id.clone())
-            .unwrap_or_else(|| panic!("Deposit not found"));
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound));
 
         if amount <= 0 || amount > deposit.amount {
             panic_with_error!(&env, ContractError::InvalidInput);
@@ -157,7 +157,7 @@ impl YieldAggregator {
             .unwrap_or_else(|| panic!("Contract not initialized"));
 
         let mut deposit = contract.deposit_data.get(deposit_id.clone())
-            .unwrap_or_else(|| panic!("Deposit not found"));
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound));
 
         if !deposit.active {
             return 0;
@@ -190,7 +190,7 @@ impl YieldAggregator {
             .unwrap_or_else(|| panic!("Contract not initialized"));
 
         contract.deposit_data.get(deposit_id)
-            .unwrap_or_else(|| panic!("Deposit not found"))
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound))
     }
 
     /// Get all deposits for a user
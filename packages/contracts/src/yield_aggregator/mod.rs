@@ -3,5 +3,5 @@
 pub mod contract;
 pub mod types;
 
-pub use contract::YieldAggregator;
-pub use types::{Deposit, YieldAllocation, PoolStats};
\ No newline at end of file
+pub use contract::{YieldAggregator, YieldAggregatorClient, MAX_EARLY_WITHDRAWAL_PENALTY_BPS, MAX_FEE_BPS, YIELD_PRECISION};
+pub use types::{ApySnapshot, ContractInfo, Deposit, DepositParams, DepositStatus, WithdrawParams, YieldAllocation, PoolInfo, PoolStats, TvlBreakdown, WithdrawalReceipt};
\ No newline at end of file
@@ -1,9 +1,12 @@
 //! Yield aggregator contract types
 
-use soroban_sdk::{Address, Env, Map, Symbol, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, Env, String};
+
+use crate::shared::{saturating_u32, split_by_percentage};
 
 /// Deposit structure representing a user's deposit
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct Deposit {
     /// Depositor's address
     pub depositor: Address,
@@ -11,22 +14,35 @@ pub struct Deposit {
     pub amount: i128,
     /// Amount allocated to insurance fund
     pub insurance_allocation: i128,
-    /// Amount allocated to yield generation
+    /// Amount allocated to yield generation (absorbs the split rounding remainder)
     pub yield_allocation: i128,
+    /// Target allocation split chosen by the depositor
+    pub allocation: YieldAllocation,
     /// Timestamp of the deposit
     pub deposit_time: u64,
     /// Total yield earned so far
     pub yield_earned: i128,
+    /// Unclaimed yield earned in the reward token, kept apart from principal
+    pub reward_earned: i128,
     /// Last yield claim timestamp
     pub last_yield_claim: u64,
+    /// Timestamp of the last yield claim or withdrawal
+    pub last_activity: u64,
+    /// Timestamp until which the deposit is locked (0 if never locked)
+    pub lock_until: u64,
     /// Associated pool ID
-    pub pool_id: Vec<u8>,
+    pub pool_id: Bytes,
     /// Deposit status
     pub status: DepositStatus,
+    /// Set when accrued yield pushed the insurance share past the rebalance threshold
+    pub needs_rebalance: bool,
+    /// Fractional yield not yet credited, scaled by `YIELD_PRECISION`
+    pub yield_remainder: i128,
 }
 
 /// Yield allocation between insurance and yield generation
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct YieldAllocation {
     /// Percentage allocated to insurance fund (0-100)
     pub insurance_percentage: u32,
@@ -34,8 +50,27 @@ pub struct YieldAllocation {
     pub yield_percentage: u32,
 }
 
-/// Pool statistics
+/// Deployed version and headline configuration of the yield aggregator
 #[derive(Clone, Debug)]
+#[contracttype]
+pub struct ContractInfo {
+    /// Semantic version of the contract
+    pub version: String,
+    /// Number of authorized operators
+    pub operator_count: u32,
+    /// Minimum deposit amount
+    pub min_deposit: i128,
+    /// Fee charged on deposits, in basis points
+    pub deposit_fee_bps: u32,
+    /// Fee charged on withdrawals, in basis points
+    pub withdraw_fee_bps: u32,
+    /// Whether the system is paused
+    pub paused: bool,
+}
+
+/// Pool statistics
+#[derive(Clone, Debug, Default)]
+#[contracttype]
 pub struct PoolStats {
     /// Total deposits in the pool
     pub total_deposits: i128,
@@ -51,8 +86,54 @@ pub struct PoolStats {
     pub current_apy: u32,
 }
 
+/// Registered yield pool and its latest metrics
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct PoolInfo {
+    /// Pool identifier
+    pub pool_id: Bytes,
+    /// Whether new deposits may be routed to the pool
+    pub enabled: bool,
+    /// Maximum total deposits the pool accepts
+    pub cap: i128,
+    /// Current total deposits in the pool
+    pub total_deposits: i128,
+    /// Current APY of the pool, in basis points
+    pub current_apy: u32,
+}
+
+impl PoolInfo {
+    /// Get the deposit capacity left before the pool hits its cap
+    pub fn remaining_cap(&self) -> i128 {
+        (self.cap - self.total_deposits).max(0)
+    }
+}
+
+/// Pool APY recorded at a point in time
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ApySnapshot {
+    /// APY at the time of the snapshot, in basis points
+    pub apy: u32,
+    /// Timestamp when the APY was recalculated
+    pub timestamp: u64,
+}
+
+/// Total value locked split by allocation type
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct TvlBreakdown {
+    /// Principal allocated to the insurance fund
+    pub total_insurance_allocation: i128,
+    /// Principal allocated to yield generation
+    pub total_yield_allocation: i128,
+    /// Realized yield earned across all deposits
+    pub total_yield_earned: i128,
+}
+
 /// Deposit status
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
 pub enum DepositStatus {
     /// Deposit is active
     Active,
@@ -64,21 +145,27 @@ pub enum DepositStatus {
 
 /// Deposit parameters
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct DepositParams {
     /// Depositor's address
     pub depositor: Address,
     /// Amount to deposit
     pub amount: i128,
     /// Pool ID to deposit into
-    pub pool_id: Vec<u8>,
+    pub pool_id: Bytes,
     /// Whether to allocate to insurance fund
     pub allocate_to_insurance: bool,
-    /// Custom allocation percentages (optional)
-    pub custom_allocation: Option<YieldAllocation>,
+    /// Custom insurance percentage (optional); the rest goes to yield
+    pub custom_insurance_percentage: Option<u32>,
+    /// Lock period in seconds (0 for a liquid deposit)
+    pub lock_duration: u64,
+    /// Address that referred the depositor, credited with a referral reward
+    pub referrer: Option<Address>,
 }
 
 /// Withdrawal parameters
 #[derive(Clone, Debug)]
+#[contracttype]
 pub struct WithdrawParams {
     /// Depositor's address
     pub depositor: Address,
@@ -90,6 +177,27 @@ pub struct WithdrawParams {
     pub from_yield: bool,
 }
 
+/// Breakdown of a completed withdrawal
+///
+/// The per-source amounts sum to the gross amount withdrawn; `net_amount` is
+/// that gross amount minus `fee`.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct WithdrawalReceipt {
+    /// Principal drawn from the insurance allocation
+    pub from_insurance: i128,
+    /// Principal drawn from the yield allocation
+    pub from_yield_principal: i128,
+    /// Realized yield drawn
+    pub from_yield_earned: i128,
+    /// Withdrawal fee charged
+    pub fee: i128,
+    /// Amount paid out after fees
+    pub net_amount: i128,
+    /// Value left in the deposit after the withdrawal
+    pub remaining_value: i128,
+}
+
 impl Default for YieldAllocation {
     fn default() -> Self {
         Self {
@@ -102,26 +210,43 @@ impl Default for YieldAllocation {
 impl Deposit {
     /// Create a new deposit
     pub fn new(params: DepositParams, default_allocation: YieldAllocation, env: &Env) -> Self {
-        let allocation = params.custom_allocation.unwrap_or(default_allocation);
+        let allocation = match params.custom_insurance_percentage {
+            Some(insurance_percentage) => YieldAllocation {
+                insurance_percentage,
+                yield_percentage: 100 - insurance_percentage,
+            },
+            None => default_allocation,
+        };
 
         // Validate allocation percentages
         if allocation.insurance_percentage + allocation.yield_percentage != 100 {
             panic!("Allocation percentages must sum to 100");
         }
 
-        let insurance_amount = (params.amount * allocation.insurance_percentage as i128) / 100;
-        let yield_amount = params.amount - insurance_amount;
+        // Rounding remainder always goes to the yield allocation
+        let (insurance_amount, yield_amount) =
+            split_by_percentage(params.amount, allocation.insurance_percentage);
 
         Self {
             depositor: params.depositor,
             amount: params.amount,
             insurance_allocation: insurance_amount,
             yield_allocation: yield_amount,
+            allocation,
             deposit_time: env.ledger().timestamp(),
             yield_earned: 0,
+            reward_earned: 0,
             last_yield_claim: env.ledger().timestamp(),
+            last_activity: env.ledger().timestamp(),
+            lock_until: if params.lock_duration > 0 {
+                env.ledger().timestamp() + params.lock_duration
+            } else {
+                0
+            },
             pool_id: params.pool_id,
             status: DepositStatus::Active,
+            needs_rebalance: false,
+            yield_remainder: 0,
         }
     }
 
@@ -130,7 +255,7 @@ impl Deposit {
         if self.amount == 0 {
             return 0;
         }
-        ((self.yield_allocation * 100) / self.amount) as u32
+        saturating_u32(self.yield_allocation.saturating_mul(100) / self.amount)
     }
 
     /// Get current insurance allocation percentage
@@ -138,7 +263,66 @@ impl Deposit {
         if self.amount == 0 {
             return 0;
         }
-        ((self.insurance_allocation * 100) / self.amount) as u32
+        saturating_u32(self.insurance_allocation.saturating_mul(100) / self.amount)
+    }
+
+    /// Get how far the insurance share of the total value is from its target, in basis points
+    pub fn insurance_drift_bps(&self) -> u32 {
+        let total_value = self.total_value();
+        if total_value <= 0 {
+            return 0;
+        }
+
+        let actual_bps = self.insurance_allocation.saturating_mul(10000) / total_value;
+        let target_bps = self.allocation.insurance_percentage as i128 * 100;
+        saturating_u32((actual_bps - target_bps).abs())
+    }
+
+    /// Re-split the principal according to a new allocation
+    ///
+    /// Returns the signed amount moved into the insurance fund (negative when
+    /// funds move out of it).
+    pub fn rebalance(&mut self, allocation: YieldAllocation) -> i128 {
+        let principal = self.insurance_allocation + self.yield_allocation;
+        let (insurance_amount, yield_amount) = split_by_percentage(principal, allocation.insurance_percentage);
+        let insurance_delta = insurance_amount - self.insurance_allocation;
+
+        self.insurance_allocation = insurance_amount;
+        self.yield_allocation = yield_amount;
+        self.allocation = allocation;
+
+        insurance_delta
+    }
+
+    /// Add funds to the deposit using its stored allocation split
+    ///
+    /// `deposit_time` becomes the amount-weighted average of the old and new
+    /// funds; `last_yield_claim` is left untouched so a top-up neither resets
+    /// nor skips the yield claim cooldown. Returns the insurance portion added.
+    pub fn top_up(&mut self, amount: i128, env: &Env) -> i128 {
+        let now = env.ledger().timestamp();
+        let (insurance_amount, yield_amount) = split_by_percentage(amount, self.allocation.insurance_percentage);
+
+        let previous_amount = self.amount;
+        self.deposit_time = ((self.deposit_time as i128 * previous_amount + now as i128 * amount)
+            / (previous_amount + amount)) as u64;
+
+        self.amount += amount;
+        self.insurance_allocation += insurance_amount;
+        self.yield_allocation += yield_amount;
+        self.last_activity = now;
+
+        insurance_amount
+    }
+
+    /// Check if the deposit is still within its lock period
+    pub fn is_locked(&self, env: &Env) -> bool {
+        env.ledger().timestamp() < self.lock_until
+    }
+
+    /// Check if the deposit was locked and its lock period has ended
+    pub fn is_matured(&self, env: &Env) -> bool {
+        self.lock_until > 0 && !self.is_locked(env)
     }
 
     /// Check if deposit is active
@@ -160,21 +344,42 @@ impl Deposit {
 
         // Annualized yield rate (simplified)
         let seconds_in_year = 365 * 24 * 60 * 60;
-        ((self.yield_earned * 100 * seconds_in_year) / (self.yield_allocation * time_elapsed)) as u32
+        saturating_u32(
+            self.yield_earned.saturating_mul(100 * seconds_in_year)
+                / self.yield_allocation.saturating_mul(time_elapsed as i128)
+        )
+    }
+
+    /// Check if the deposit has been inactive for at least `threshold` seconds
+    pub fn is_inactive(&self, env: &Env, threshold: u64) -> bool {
+        env.ledger().timestamp().saturating_sub(self.last_activity) >= threshold
     }
 
     /// Add yield to the deposit
     pub fn add_yield(&mut self, yield_amount: i128, env: &Env) {
         self.yield_earned += yield_amount;
         self.last_yield_claim = env.ledger().timestamp();
+        self.last_activity = env.ledger().timestamp();
     }
 
     /// Withdraw amount from deposit
-    pub fn withdraw(&mut self, amount: i128, from_insurance: bool, from_yield: bool) -> i128 {
+    ///
+    /// Returns a receipt with the amount taken from each source; the fee and
+    /// net amount are left for the caller to fill in.
+    pub fn withdraw(&mut self, amount: i128, from_insurance: bool, from_yield: bool) -> WithdrawalReceipt {
         if amount <= 0 {
             panic!("Withdrawal amount must be positive");
         }
 
+        let mut receipt = WithdrawalReceipt {
+            from_insurance: 0,
+            from_yield_principal: 0,
+            from_yield_earned: 0,
+            fee: 0,
+            net_amount: amount,
+            remaining_value: 0,
+        };
+
         if from_insurance && from_yield {
             // Withdraw from both allocations proportionally
             let insurance_withdrawal = (amount * self.insurance_allocation) / self.total_value();
@@ -182,12 +387,15 @@ impl Deposit {
 
             self.insurance_allocation -= insurance_withdrawal;
             self.yield_allocation -= yield_withdrawal;
+            receipt.from_insurance = insurance_withdrawal;
+            receipt.from_yield_principal = yield_withdrawal;
         } else if from_insurance {
             // Withdraw only from insurance allocation
             if amount > self.insurance_allocation {
                 panic!("Insufficient insurance allocation");
             }
             self.insurance_allocation -= amount;
+            receipt.from_insurance = amount;
         } else if from_yield {
             // Withdraw only from yield allocation
             if amount > self.yield_allocation + self.yield_earned {
@@ -195,11 +403,13 @@ impl Deposit {
             }
 
             // First withdraw from yield earned, then from principal
-            let available_yield = self.yield_allocation + self.yield_earned;
             if amount <= self.yield_earned {
                 self.yield_earned -= amount;
+                receipt.from_yield_earned = amount;
             } else {
                 let principal_withdrawal = amount - self.yield_earned;
+                receipt.from_yield_earned = self.yield_earned;
+                receipt.from_yield_principal = principal_withdrawal;
                 self.yield_earned = 0;
                 self.yield_allocation -= principal_withdrawal;
             }
@@ -212,7 +422,8 @@ impl Deposit {
             self.status = DepositStatus::Withdrawn;
         }
 
-        amount
+        receipt.remaining_value = self.total_value();
+        receipt
     }
 }
 
@@ -260,8 +471,17 @@ impl PoolStats {
         }
 
         // Simplified APY calculation
-        let yield_rate = (self.total_yield_earned * 10000) / self.total_yield_allocation;
-        self.current_apy = yield_rate as u32;
+        let yield_rate = self.total_yield_earned.saturating_mul(10000) / self.total_yield_allocation;
+        self.current_apy = saturating_u32(yield_rate);
+    }
+
+    /// Get the total value locked broken down by allocation
+    pub fn tvl_breakdown(&self) -> TvlBreakdown {
+        TvlBreakdown {
+            total_insurance_allocation: self.total_insurance_allocation,
+            total_yield_allocation: self.total_yield_allocation,
+            total_yield_earned: self.total_yield_earned,
+        }
     }
 
     /// Get insurance fund percentage
@@ -269,6 +489,6 @@ impl PoolStats {
         if self.total_deposits == 0 {
             return 0;
         }
-        ((self.total_insurance_allocation * 100) / self.total_deposits) as u32
+        saturating_u32(self.total_insurance_allocation.saturating_mul(100) / self.total_deposits)
     }
 }
\ No newline at end of file
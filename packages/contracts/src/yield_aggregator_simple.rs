@@ -1,6 +1,8 @@
 //! Simple Yield Aggregator Contract (No Constructor Version)
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, Symbol, Vec, String};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, Symbol, Vec, Bytes, panic_with_error};
+
+use crate::shared::{generate_id, next_id_nonce, ContractError};
 
 /// Simplified yield allocation
 #[derive(Clone, Debug)]
@@ -35,13 +37,16 @@ pub struct YieldAggregator;
 #[contractimpl]
 impl YieldAggregator {
     /// Create a new deposit
-    pub fn deposit(env: Env, depositor: Address, amount: i128, insurance_percentage: u32) -> u64 {
-        // Simple deposit ID generation
-        let deposit_id: u64 = env.ledger().sequence().into();
+    pub fn deposit(env: Env, depositor: Address, amount: i128, insurance_percentage: u32) -> Bytes {
+        if amount <= 0 || insurance_percentage > 100 {
+            panic_with_error!(&env, ContractError::InvalidInput);
+        }
+
+        let deposit_id = generate_id(&env, Symbol::new(&env, "deposit"), &depositor, next_id_nonce(&env));
 
         // Create allocation
         let allocation = YieldAllocation {
-            insurance_percentage: insurance_percentage,
+            insurance_percentage,
             yield_percentage: 100 - insurance_percentage,
         };
 
@@ -54,19 +59,19 @@ impl YieldAggregator {
         };
 
         // Store in storage
-        let mut deposits: Map<u64, Deposit> = env.storage().instance()
+        let mut deposits: Map<Bytes, Deposit> = env.storage().instance()
             .get(&Symbol::new(&env, "deposits"))
             .unwrap_or(Map::new(&env));
 
-        deposits.set(deposit_id, deposit);
+        deposits.set(deposit_id.clone(), deposit);
         env.storage().instance().set(&Symbol::new(&env, "deposits"), &deposits);
 
         // Update user deposits
-        let mut user_deposits: Vec<u64> = env.storage().instance()
+        let mut user_deposits: Vec<Bytes> = env.storage().instance()
             .get(&Symbol::new(&env, "user_deposits"))
             .unwrap_or(Vec::new(&env));
 
-        user_deposits.push_back(deposit_id);
+        user_deposits.push_back(deposit_id.clone());
         env.storage().instance().set(&Symbol::new(&env, "user_deposits"), &user_deposits);
 
         // Update stats
@@ -86,12 +91,12 @@ impl YieldAggregator {
     }
 
     /// Withdraw a deposit
-    pub fn withdraw(env: Env, deposit_id: u64, amount: i128) -> bool {
-        let mut deposits: Map<u64, Deposit> = env.storage().instance()
+    pub fn withdraw(env: Env, deposit_id: Bytes, amount: i128) -> bool {
+        let mut deposits: Map<Bytes, Deposit> = env.storage().instance()
             .get(&Symbol::new(&env, "deposits"))
             .unwrap_or(Map::new(&env));
 
-        if let Some(deposit) = deposits.get(deposit_id) {
+        if let Some(deposit) = deposits.get(deposit_id.clone()) {
             if deposit.amount >= amount {
                 if deposit.amount == amount {
                     // Remove deposit entirely
@@ -128,27 +133,16 @@ impl YieldAggregator {
     }
 
     /// Get deposit information
-    pub fn get_deposit(env: Env, deposit_id: u64) -> Deposit {
-        let deposits: Map<u64, Deposit> = env.storage().instance()
+    pub fn get_deposit(env: Env, deposit_id: Bytes) -> Deposit {
+        let deposits: Map<Bytes, Deposit> = env.storage().instance()
             .get(&Symbol::new(&env, "deposits"))
             .unwrap_or(Map::new(&env));
 
-        deposits.get(deposit_id).unwrap_or_else(|| {
-            // Return empty deposit if not found
-            Deposit {
-                depositor: Address::from_string(&String::from_str(&env, "GDQD3UOVCPUTS32XS37N6BJGWAXCARWH7YIDTZUAWMHQEGBXIM3HQ66YV")),
-                amount: 0,
-                allocation: YieldAllocation {
-                    insurance_percentage: 0,
-                    yield_percentage: 100,
-                },
-                deposit_time: 0,
-            }
-        })
+        deposits.get(deposit_id).unwrap_or_else(|| panic_with_error!(&env, ContractError::NotFound))
     }
 
     /// Get all deposits for a user
-    pub fn get_user_deposits(env: Env, _user: Address) -> Vec<u64> {
+    pub fn get_user_deposits(env: Env, _user: Address) -> Vec<Bytes> {
         env.storage().instance()
             .get(&Symbol::new(&env, "user_deposits"))
             .unwrap_or(Vec::new(&env))
@@ -166,12 +160,12 @@ impl YieldAggregator {
     }
 
     /// Add yield to a deposit
-    pub fn add_yield(env: Env, deposit_id: u64, yield_amount: i128) -> bool {
-        let mut deposits: Map<u64, Deposit> = env.storage().instance()
+    pub fn add_yield(env: Env, deposit_id: Bytes, yield_amount: i128) -> bool {
+        let mut deposits: Map<Bytes, Deposit> = env.storage().instance()
             .get(&Symbol::new(&env, "deposits"))
             .unwrap_or(Map::new(&env));
 
-        if let Some(deposit) = deposits.get(deposit_id) {
+        if let Some(deposit) = deposits.get(deposit_id.clone()) {
             // Add yield to allocation based on percentages
             let _insurance_yield = yield_amount * deposit.allocation.insurance_percentage as i128 / 100;
             let _regular_yield = yield_amount * deposit.allocation.yield_percentage as i128 / 100;
@@ -204,8 +198,8 @@ impl YieldAggregator {
     }
 
     /// Check if a deposit exists
-    pub fn deposit_exists(env: Env, deposit_id: u64) -> bool {
-        let deposits: Map<u64, Deposit> = env.storage().instance()
+    pub fn deposit_exists(env: Env, deposit_id: Bytes) -> bool {
+        let deposits: Map<Bytes, Deposit> = env.storage().instance()
             .get(&Symbol::new(&env, "deposits"))
             .unwrap_or(Map::new(&env));
 
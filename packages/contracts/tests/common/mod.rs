@@ -0,0 +1,63 @@
+//! Deployment fixtures shared by the full-contract integration tests
+//!
+//! SDK 21 has no deploy-time constructors and the host rejects `__`-prefixed
+//! invocations, so each fixture runs the constructor in its contract's context.
+
+#![allow(dead_code)]
+
+use contracts::insurance::{InsuranceContract, InsuranceContractClient};
+use contracts::treasury::{Treasury, TreasuryClient};
+use contracts::yield_aggregator::{YieldAggregator, YieldAggregatorClient};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, Env, Vec};
+
+/// Deploy and construct an insurance contract with whole-unit token amounts
+pub fn deploy_insurance<'a>(
+    env: &'a Env,
+    admin: &Address,
+    guardian: &Address,
+    min_risk_pool: i128,
+) -> InsuranceContractClient<'a> {
+    let contract_id = env.register_contract(None, InsuranceContract);
+    env.as_contract(&contract_id, || {
+        InsuranceContract::__constructor(env.clone(), admin.clone(), guardian.clone(), 0, min_risk_pool);
+    });
+    InsuranceContractClient::new(env, &contract_id)
+}
+
+/// Deploy and construct a treasury contract
+pub fn deploy_treasury<'a>(
+    env: &'a Env,
+    owner: &Address,
+    admins: Vec<Address>,
+    guardian: &Address,
+) -> TreasuryClient<'a> {
+    let contract_id = env.register_contract(None, Treasury);
+    env.as_contract(&contract_id, || {
+        Treasury::__constructor(env.clone(), owner.clone(), admins, guardian.clone());
+    });
+    TreasuryClient::new(env, &contract_id)
+}
+
+/// Deploy and construct a yield aggregator sending 20% of deposits to insurance
+pub fn deploy_yield_aggregator<'a>(
+    env: &'a Env,
+    admin: &Address,
+    insurance: &Address,
+    treasury: &Address,
+    guardian: &Address,
+) -> YieldAggregatorClient<'a> {
+    let contract_id = env.register_contract(None, YieldAggregator);
+    env.as_contract(&contract_id, || {
+        YieldAggregator::__constructor(
+            env.clone(),
+            admin.clone(),
+            Address::generate(env),
+            insurance.clone(),
+            treasury.clone(),
+            20,
+            guardian.clone(),
+        );
+    });
+    YieldAggregatorClient::new(env, &contract_id)
+}
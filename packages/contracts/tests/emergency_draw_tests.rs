@@ -0,0 +1,117 @@
+//! Cross-contract tests for insurance payouts topped up from the treasury emergency fund
+
+mod common;
+
+use contracts::insurance::{ClaimEvidence, CreatePolicyParams, InsuranceContractClient};
+use contracts::shared::ClaimStatus;
+use contracts::treasury::TreasuryClient;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{vec, Address, Bytes, Env, Symbol};
+
+const SETTLEMENT_DELAY: u64 = 86400;
+
+struct Stack<'a> {
+    admin: Address,
+    insurance: InsuranceContractClient<'a>,
+    treasury: TreasuryClient<'a>,
+}
+
+fn deploy(env: &Env, emergency_draw_enabled: bool) -> Stack<'_> {
+    let admin = Address::generate(env);
+    let guardian = Address::generate(env);
+    let stack = Stack {
+        insurance: common::deploy_insurance(env, &admin, &guardian, 0),
+        treasury: common::deploy_treasury(env, &admin, vec![env, admin.clone()], &guardian),
+        admin,
+    };
+
+    let mut config = stack.insurance.get_config();
+    config.emergency_draw_enabled = emergency_draw_enabled;
+    stack.insurance.update_config(&stack.admin, &config);
+    stack.insurance.update_treasury(&stack.admin, &Some(stack.treasury.address.clone()));
+    stack.treasury.update_emergency_drawer(&stack.admin, &Some(stack.insurance.address.clone()));
+    stack
+}
+
+/// Approve a claim for 800 against a 1000 policy and let its settlement delay pass
+fn large_approved_claim(env: &Env, stack: &Stack) -> Bytes {
+    let holder = Address::generate(env);
+    let policy_id = Bytes::from_slice(env, b"policy-1");
+    let claim_id = Bytes::from_slice(env, b"claim-1");
+    stack.insurance.fund_risk_pool(&stack.admin, &5000);
+    stack.insurance.create_policy(
+        &policy_id,
+        &CreatePolicyParams {
+            holder: holder.clone(),
+            coverage_amount: 1000,
+            duration: 31536000,
+            risk_score: 10,
+            pool_id: Bytes::from_slice(env, b"pool"),
+            premium: 100,
+            beneficiary: None,
+            deductible: 0,
+            declining: false,
+        },
+    );
+    stack.insurance.pay_premium(&policy_id, &100);
+    stack.insurance.submit_claim(
+        &holder,
+        &claim_id,
+        &policy_id,
+        &800,
+        &ClaimEvidence {
+            evidence_type: Symbol::new(env, "photo"),
+            data: Bytes::from_slice(env, b"evidence"),
+            timestamp: env.ledger().timestamp(),
+            notes: Symbol::new(env, "loss"),
+            market_value: None,
+        },
+        &None,
+    );
+    stack.insurance.process_claim(&claim_id, &true, &stack.admin, &Symbol::new(env, "approved"), &None);
+    env.ledger().with_mut(|ledger| ledger.timestamp += SETTLEMENT_DELAY);
+
+    // A loss on the pool's assets after approval leaves it short of the payout
+    stack.insurance.record_risk_pool_loss(&stack.admin, &4700);
+    claim_id
+}
+
+#[test]
+fn test_large_claim_draws_shortfall_from_treasury_emergency_fund() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let stack = deploy(&env, true);
+    stack.treasury.add_funds(&stack.admin, &10000, &Symbol::new(&env, "seed"));
+    assert_eq!(stack.treasury.get_stats().emergency_fund_balance, 1000);
+    let claim_id = large_approved_claim(&env, &stack);
+
+    stack.insurance.pay_claim(&claim_id);
+
+    assert_eq!(stack.insurance.get_claim(&claim_id).status, ClaimStatus::Paid);
+    assert_eq!(stack.treasury.get_stats().emergency_fund_balance, 500);
+    assert_eq!(stack.insurance.get_risk_pool_balance(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_large_claim_fails_without_emergency_draw() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let stack = deploy(&env, false);
+    stack.treasury.add_funds(&stack.admin, &10000, &Symbol::new(&env, "seed"));
+    let claim_id = large_approved_claim(&env, &stack);
+
+    stack.insurance.pay_claim(&claim_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_large_claim_fails_when_emergency_fund_is_short() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let stack = deploy(&env, true);
+    stack.treasury.add_funds(&stack.admin, &4000, &Symbol::new(&env, "seed"));
+    let claim_id = large_approved_claim(&env, &stack);
+
+    stack.insurance.pay_claim(&claim_id);
+}
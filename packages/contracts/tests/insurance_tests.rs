@@ -0,0 +1,1561 @@
+//! Integration tests for the full InsuranceContract
+
+mod common;
+
+use contracts::insurance::types::RATE_SCALE;
+use contracts::insurance::{
+    ClaimEvidence, CreatePolicyParams, FundSource, InsuranceContract, InsuranceContractClient, RejectionReason,
+};
+use contracts::shared::{ClaimStatus, PolicyStatus};
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::{contract, contractimpl, vec, Address, Bytes, Env, Symbol, TryFromVal};
+
+const SETTLEMENT_DELAY: u64 = 86400;
+
+fn setup(env: &Env) -> (InsuranceContractClient<'_>, Address) {
+    let admin = Address::generate(env);
+    (common::deploy_insurance(env, &admin, &Address::generate(env), 0), admin)
+}
+
+fn policy_params(env: &Env, holder: &Address, coverage: i128, premium: i128) -> CreatePolicyParams {
+    CreatePolicyParams {
+        holder: holder.clone(),
+        coverage_amount: coverage,
+        duration: 31536000,
+        risk_score: 10,
+        pool_id: Bytes::from_slice(env, b"pool"),
+        premium,
+        beneficiary: None,
+        deductible: 0,
+        declining: false,
+    }
+}
+
+fn create_policy(
+    env: &Env,
+    client: &InsuranceContractClient,
+    policy_id: &[u8],
+    holder: &Address,
+    coverage: i128,
+    premium: i128,
+) -> Bytes {
+    let policy_id = Bytes::from_slice(env, policy_id);
+    client.create_policy(&policy_id, &policy_params(env, holder, coverage, premium));
+    policy_id
+}
+
+fn create_paid_policy(env: &Env, client: &InsuranceContractClient, holder: &Address, coverage: i128, premium: i128) -> Bytes {
+    let policy_id = create_policy(env, client, b"policy-1", holder, coverage, premium);
+    client.pay_premium(&policy_id, &premium);
+    policy_id
+}
+
+fn evidence(env: &Env) -> ClaimEvidence {
+    ClaimEvidence {
+        evidence_type: Symbol::new(env, "photo"),
+        data: Bytes::from_slice(env, b"evidence"),
+        timestamp: env.ledger().timestamp(),
+        notes: Symbol::new(env, "loss"),
+        market_value: None,
+    }
+}
+
+fn approved_claim(
+    env: &Env,
+    client: &InsuranceContractClient,
+    admin: &Address,
+    holder: &Address,
+    policy_id: &Bytes,
+    claim_id: &[u8],
+    amount: i128,
+) -> Bytes {
+    let claim_id = Bytes::from_slice(env, claim_id);
+    client.submit_claim(holder, &claim_id, policy_id, &amount, &evidence(env), &None);
+    client.process_claim(&claim_id, &true, admin, &Symbol::new(env, "approved"), &None);
+    claim_id
+}
+
+fn advance_time(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|ledger| ledger.timestamp += seconds);
+}
+
+/// Get the payee and amount of the `claim_paid` event emitted by the last invocation
+fn claim_paid(env: &Env) -> (Address, i128) {
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            Symbol::try_from_val(env, &topics.get(0).unwrap()) == Ok(Symbol::new(env, "claim_paid"))
+        })
+        .unwrap();
+    let (payee, amount, _): (Address, i128, Option<Symbol>) = <_>::try_from_val(env, &data).unwrap();
+    (payee, amount)
+}
+
+#[test]
+fn test_batch_payout_skips_repeated_claim_id() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let holder = Address::generate(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 300);
+    advance_time(&env, SETTLEMENT_DELAY);
+
+    let summary = client.pay_claims_batch(&admin, &vec![&env, claim_id.clone(), claim_id.clone()]);
+
+    assert_eq!(summary.paid.len(), 1);
+    assert_eq!(summary.skipped.len(), 1);
+    assert_eq!(summary.total_paid, 300);
+    assert_eq!(client.get_risk_pool_balance(), 4700);
+    assert_eq!(client.get_policy(&policy_id).claimed_amount, 300);
+}
+
+#[test]
+fn test_risk_contribution_is_collected_at_creation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let mut config = client.get_config();
+    config.creation_risk_contribution_bps = 1000;
+    client.update_config(&admin, &config);
+    let holder = Address::generate(&env);
+
+    let policy_id = create_policy(&env, &client, b"policy-1", &holder, 1000, 100);
+    assert_eq!(client.get_risk_pool_balance(), 10);
+    assert_eq!(client.get_funding_breakdown().premiums, 10);
+
+    // The contribution counts toward the premium; the rest goes to the premium pool
+    assert_eq!(client.get_policy(&policy_id).premium_paid_so_far, 10);
+    client.pay_premium(&policy_id, &90);
+
+    assert_eq!(client.get_risk_pool_balance(), 10);
+    assert_eq!(client.get_premium_pool_balance(), 90);
+    assert!(client.get_policy(&policy_id).is_premium_paid());
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_process_claim_requires_processor_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let holder = Address::generate(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    let claim_id = Bytes::from_slice(&env, b"claim-1");
+    client.submit_claim(&holder, &claim_id, &policy_id, &300, &evidence(&env), &None);
+
+    env.set_auths(&[]);
+    client.process_claim(&claim_id, &true, &admin, &Symbol::new(&env, "approved"), &None);
+}
+
+#[test]
+fn test_claim_exhausting_remaining_coverage_closes_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let holder = Address::generate(&env);
+    client.fund_risk_pool(&admin, &5000);
+    // Coverage of 1000 at risk score 10 is 900 effective
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+
+    let first_claim = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 500);
+    advance_time(&env, SETTLEMENT_DELAY);
+    client.pay_claim(&first_claim);
+    assert_eq!(client.get_policy(&policy_id).status, PolicyStatus::Active);
+
+    let second_claim = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-2", 400);
+    advance_time(&env, SETTLEMENT_DELAY);
+    client.pay_claim(&second_claim);
+
+    let policy = client.get_policy(&policy_id);
+    assert_eq!(policy.claimed_amount, 900);
+    assert_eq!(policy.status, PolicyStatus::Claimed);
+}
+
+#[test]
+fn test_total_loss_refund_is_capped_at_premium_paid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let mut config = client.get_config();
+    config.premium_installments = 2;
+    client.update_config(&admin, &config);
+    let holder = Address::generate(&env);
+    client.fund_risk_pool(&admin, &5000);
+    // Another holder's payment keeps the premium pool above this policy's refund
+    let other_policy = create_policy(&env, &client, b"policy-2", &Address::generate(&env), 1000, 100);
+    client.pay_premium(&other_policy, &50);
+
+    // Only the first of two installments is paid
+    let policy_id = create_policy(&env, &client, b"policy-1", &holder, 1000, 100);
+    client.pay_premium(&policy_id, &50);
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 900);
+    advance_time(&env, SETTLEMENT_DELAY);
+
+    client.pay_claim(&claim_id);
+
+    assert_eq!(client.get_policy(&policy_id).status, PolicyStatus::Claimed);
+    assert_eq!(client.get_premium_pool_balance(), 50);
+}
+
+#[test]
+fn test_pay_claim_only_needs_pool_to_cover_net_payout() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let mut config = client.get_config();
+    config.absolute_payout_cap = 300;
+    client.update_config(&admin, &config);
+    let holder = Address::generate(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 500);
+    advance_time(&env, SETTLEMENT_DELAY);
+
+    // A loss leaves the pool below the gross claim but above its capped payout
+    client.record_risk_pool_loss(&admin, &4650);
+    client.pay_claim(&claim_id);
+
+    assert_eq!(client.get_risk_pool_balance(), 50);
+    assert_eq!(client.get_policy(&policy_id).claimed_amount, 300);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_update_config_requires_admin_auth() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    client.update_config(&admin, &client.get_config());
+}
+
+#[test]
+fn test_owner_rolls_back_config_to_snapshot() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let mut config = client.get_config();
+    config.max_coverage = 5000;
+    client.update_config(&admin, &config);
+    let snapshot_id = client.snapshot_config(&admin);
+    config.max_coverage = 50;
+    client.update_config(&admin, &config);
+
+    client.rollback_config(&admin, &snapshot_id);
+
+    assert_eq!(env.auths()[0].0, client.get_owner());
+    assert_eq!(client.get_config().max_coverage, 5000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_rollback_config_rejects_non_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let snapshot_id = client.snapshot_config(&admin);
+
+    client.rollback_config(&Address::generate(&env), &snapshot_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_pending_claims_reserve_the_risk_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &1000);
+    let holder = Address::generate(&env);
+    for (policy_id, claim_id, amount) in [(b"policy-1", b"claim-1", 600), (b"policy-2", b"claim-2", 400)] {
+        let policy_id = create_policy(&env, &client, policy_id, &holder, 1000, 100);
+        client.pay_premium(&policy_id, &100);
+        client.submit_claim(&holder, &Bytes::from_slice(&env, claim_id), &policy_id, &amount, &evidence(&env), &None);
+    }
+    assert_eq!(client.get_reserved_balance(), 1000);
+    assert_eq!(client.get_available_risk_pool_balance(), 0);
+
+    let policy_id = create_policy(&env, &client, b"policy-3", &holder, 1000, 100);
+    client.pay_premium(&policy_id, &100);
+    client.submit_claim(&holder, &Bytes::from_slice(&env, b"claim-3"), &policy_id, &1, &evidence(&env), &None);
+}
+
+#[test]
+fn test_claim_pays_beneficiary_when_set() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let lender = Address::generate(&env);
+    let policy_id = Bytes::from_slice(&env, b"policy-1");
+    client.create_policy(
+        &policy_id,
+        &CreatePolicyParams { beneficiary: Some(lender.clone()), ..policy_params(&env, &holder, 1000, 100) },
+    );
+    client.pay_premium(&policy_id, &100);
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 300);
+    advance_time(&env, SETTLEMENT_DELAY);
+
+    client.pay_claim(&claim_id);
+
+    assert_eq!(claim_paid(&env).0, lender);
+}
+
+#[test]
+fn test_claim_pays_holder_without_beneficiary() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 300);
+    advance_time(&env, SETTLEMENT_DELAY);
+
+    client.pay_claim(&claim_id);
+
+    assert_eq!(claim_paid(&env).0, holder);
+}
+
+#[test]
+fn test_rejection_stats_count_each_reason() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let rejections = [
+        (b"policy-1", b"claim-1", RejectionReason::InsufficientEvidence),
+        (b"policy-2", b"claim-2", RejectionReason::InsufficientEvidence),
+        (b"policy-3", b"claim-3", RejectionReason::OutOfCoverage),
+    ];
+    for (policy_id, claim_id, rejection_reason) in rejections {
+        let policy_id = create_policy(&env, &client, policy_id, &holder, 1000, 100);
+        client.pay_premium(&policy_id, &100);
+        let claim_id = Bytes::from_slice(&env, claim_id);
+        client.submit_claim(&holder, &claim_id, &policy_id, &100, &evidence(&env), &None);
+        client.process_claim(&claim_id, &false, &admin, &Symbol::new(&env, "rejected"), &Some(rejection_reason));
+    }
+
+    let stats = client.get_rejection_stats();
+
+    assert_eq!(stats.insufficient_evidence, 2);
+    assert_eq!(stats.out_of_coverage, 1);
+    assert_eq!(stats.fraud_suspected, 0);
+    assert_eq!(client.get_claim(&Bytes::from_slice(&env, b"claim-3")).rejection_reason, RejectionReason::OutOfCoverage);
+}
+
+fn expired_policy(env: &Env, client: &InsuranceContractClient, holder: &Address, expired_for: u64) -> Bytes {
+    let policy_id = Bytes::from_slice(env, b"policy-1");
+    client.create_policy(&policy_id, &CreatePolicyParams { duration: 86400, ..policy_params(env, holder, 1000, 100) });
+    client.pay_premium(&policy_id, &100);
+    advance_time(env, 86400 + expired_for);
+    policy_id
+}
+
+#[test]
+fn test_renew_policy_inside_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let holder = Address::generate(&env);
+    let grace_period = client.get_config().renewal_grace_period;
+    let policy_id = expired_policy(&env, &client, &holder, grace_period - 1);
+
+    client.renew_policy(&policy_id, &86400, &100);
+
+    let policy = client.get_policy(&policy_id);
+    assert_eq!(policy.start_time, env.ledger().timestamp());
+    assert_eq!(policy.status, PolicyStatus::Active);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_renew_policy_past_grace_period_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let holder = Address::generate(&env);
+    let grace_period = client.get_config().renewal_grace_period;
+    let policy_id = expired_policy(&env, &client, &holder, grace_period);
+
+    client.renew_policy(&policy_id, &86400, &100);
+}
+
+#[test]
+fn test_policy_claims_lists_every_claim_on_the_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    let unclaimed_policy = create_policy(&env, &client, b"policy-2", &holder, 1000, 100);
+
+    let first_claim = Bytes::from_slice(&env, b"claim-1");
+    client.submit_claim(&holder, &first_claim, &policy_id, &100, &evidence(&env), &None);
+    advance_time(&env, client.get_config().claim_cooldown);
+    let second_claim = Bytes::from_slice(&env, b"claim-2");
+    client.submit_claim(&holder, &second_claim, &policy_id, &200, &evidence(&env), &None);
+
+    assert_eq!(client.get_policy_claims(&policy_id), vec![&env, first_claim, second_claim]);
+    assert!(client.get_policy_claims(&unclaimed_policy).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_claim_within_cooldown_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    client.submit_claim(&holder, &Bytes::from_slice(&env, b"claim-1"), &policy_id, &100, &evidence(&env), &None);
+    advance_time(&env, client.get_config().claim_cooldown - 1);
+
+    client.submit_claim(&holder, &Bytes::from_slice(&env, b"claim-2"), &policy_id, &100, &evidence(&env), &None);
+}
+
+#[test]
+fn test_claim_after_cooldown_is_accepted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    client.submit_claim(&holder, &Bytes::from_slice(&env, b"claim-1"), &policy_id, &100, &evidence(&env), &None);
+    advance_time(&env, client.get_config().claim_cooldown);
+
+    client.submit_claim(&holder, &Bytes::from_slice(&env, b"claim-2"), &policy_id, &100, &evidence(&env), &None);
+
+    assert_eq!(client.get_policy(&policy_id).pending_claims, 2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_pay_claim_before_settlement_delay_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 300);
+    advance_time(&env, SETTLEMENT_DELAY - 1);
+
+    client.pay_claim(&claim_id);
+}
+
+#[test]
+fn test_pay_claim_after_settlement_delay() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 300);
+    assert_eq!(client.get_claim(&claim_id).payable_after, Some(env.ledger().timestamp() + SETTLEMENT_DELAY));
+    advance_time(&env, SETTLEMENT_DELAY);
+
+    client.pay_claim(&claim_id);
+
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::Paid);
+    assert_eq!(client.get_risk_pool_balance(), 4700);
+}
+
+#[test]
+fn test_veto_releases_reserved_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 300);
+    assert_eq!(client.get_reserved_balance(), 300);
+
+    client.veto_claim(&admin, &claim_id, &Symbol::new(&env, "suspicious"));
+
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::Rejected);
+    assert_eq!(client.get_reserved_balance(), 0);
+    assert_eq!(client.get_available_risk_pool_balance(), 5000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_veto_after_payout_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 300);
+    advance_time(&env, SETTLEMENT_DELAY);
+    client.pay_claim(&claim_id);
+
+    client.veto_claim(&admin, &claim_id, &Symbol::new(&env, "suspicious"));
+}
+
+#[test]
+fn test_quote_premium_surcharges_high_utilization() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    // 10% utilization quotes the base price
+    client.fund_risk_pool(&admin, &100_000);
+    assert_eq!(client.quote_premium(&10000, &31536000, &50), 500);
+
+    // Writing 80,000 of coverage takes the next quote to 90% utilization,
+    // which adds the 20% surcharge
+    let holder = Address::generate(&env);
+    for policy_id in [b"policy-1", b"policy-2", b"policy-3", b"policy-4", b"policy-5", b"policy-6", b"policy-7", b"policy-8"] {
+        create_policy(&env, &client, policy_id, &holder, 10000, 500);
+    }
+    assert_eq!(client.quote_premium(&10000, &31536000, &50), 600);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_create_policy_rejects_zero_duration() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let params = CreatePolicyParams { duration: 0, ..policy_params(&env, &Address::generate(&env), 1000, 100) };
+
+    client.create_policy(&Bytes::from_slice(&env, b"policy-1"), &params);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_create_policy_rejects_duration_below_minimum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let params = CreatePolicyParams { duration: 86399, ..policy_params(&env, &Address::generate(&env), 1000, 100) };
+
+    client.create_policy(&Bytes::from_slice(&env, b"policy-1"), &params);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_create_policy_rejects_duration_above_maximum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let params = CreatePolicyParams { duration: 31536001, ..policy_params(&env, &Address::generate(&env), 1000, 100) };
+
+    client.create_policy(&Bytes::from_slice(&env, b"policy-1"), &params);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_create_policy_rejects_overflowing_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    env.ledger().with_mut(|ledger| ledger.timestamp = u64::MAX - 1000);
+    let params = CreatePolicyParams { duration: 86400, ..policy_params(&env, &Address::generate(&env), 1000, 100) };
+
+    client.create_policy(&Bytes::from_slice(&env, b"policy-1"), &params);
+}
+
+#[test]
+fn test_claim_reference_is_stored_and_emitted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    let claim_id = Bytes::from_slice(&env, b"claim-1");
+    let reference = Some(Symbol::new(&env, "ticket_42"));
+
+    client.submit_claim(&holder, &claim_id, &policy_id, &300, &evidence(&env), &reference);
+
+    assert_eq!(client.get_claim(&claim_id).reference, reference);
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()) == Ok(Symbol::new(&env, "claim_submitted"))
+        })
+        .unwrap();
+    let (_, _, _, emitted): (Bytes, Address, i128, Option<Symbol>) = <_>::try_from_val(&env, &data).unwrap();
+    assert_eq!(emitted, reference);
+}
+
+#[test]
+fn test_migrate_policy_to_registered_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let policy_id = create_paid_policy(&env, &client, &Address::generate(&env), 1000, 100);
+    let old_pool = Bytes::from_slice(&env, b"pool");
+    let new_pool = Bytes::from_slice(&env, b"pool-2");
+    client.register_pool(&admin, &new_pool);
+
+    client.migrate_policy_pool(&admin, &policy_id, &new_pool);
+
+    assert_eq!(client.get_policy(&policy_id).pool_id, new_pool);
+    assert_eq!(client.get_pool_exposure(&old_pool), 0);
+    assert_eq!(client.get_pool_exposure(&new_pool), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_migrate_policy_to_unregistered_pool_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let policy_id = create_paid_policy(&env, &client, &Address::generate(&env), 1000, 100);
+
+    client.migrate_policy_pool(&admin, &policy_id, &Bytes::from_slice(&env, b"pool-2"));
+}
+
+fn set_min_premium_bps(client: &InsuranceContractClient, admin: &Address, min_premium_bps: u32) {
+    let mut config = client.get_config();
+    config.min_premium_bps = min_premium_bps;
+    client.update_config(admin, &config);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_underpriced_policy_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    set_min_premium_bps(&client, &admin, 500);
+
+    create_policy(&env, &client, b"policy-1", &Address::generate(&env), 1000, 49);
+}
+
+#[test]
+fn test_policy_at_minimum_premium_ratio_is_accepted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    set_min_premium_bps(&client, &admin, 500);
+
+    let policy_id = create_policy(&env, &client, b"policy-1", &Address::generate(&env), 1000, 50);
+
+    assert_eq!(client.get_policy(&policy_id).premium, 50);
+}
+
+/// Fill a policy's pending claim queue up to the default limit of three
+fn policy_with_full_claim_queue(env: &Env, client: &InsuranceContractClient, admin: &Address, holder: &Address) -> Bytes {
+    let mut config = client.get_config();
+    config.claim_cooldown = 0;
+    client.update_config(admin, &config);
+    client.fund_risk_pool(admin, &5000);
+    let policy_id = create_paid_policy(env, client, holder, 1000, 100);
+    for claim_id in [b"claim-1", b"claim-2", b"claim-3"] {
+        client.submit_claim(holder, &Bytes::from_slice(env, claim_id), &policy_id, &100, &evidence(env), &None);
+    }
+    policy_id
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_claim_over_pending_limit_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let holder = Address::generate(&env);
+    let policy_id = policy_with_full_claim_queue(&env, &client, &admin, &holder);
+
+    client.submit_claim(&holder, &Bytes::from_slice(&env, b"claim-4"), &policy_id, &100, &evidence(&env), &None);
+}
+
+#[test]
+fn test_processing_a_claim_frees_a_pending_slot() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let holder = Address::generate(&env);
+    let policy_id = policy_with_full_claim_queue(&env, &client, &admin, &holder);
+    client.process_claim(&Bytes::from_slice(&env, b"claim-1"), &false, &admin, &Symbol::new(&env, "rejected"), &None);
+
+    client.submit_claim(&holder, &Bytes::from_slice(&env, b"claim-4"), &policy_id, &100, &evidence(&env), &None);
+
+    assert_eq!(client.get_policy(&policy_id).pending_claims, 3);
+}
+
+fn claim_with_three_evidence_items(env: &Env, client: &InsuranceContractClient, admin: &Address) -> Bytes {
+    let mut config = client.get_config();
+    config.max_evidence_items = 3;
+    client.update_config(admin, &config);
+    client.fund_risk_pool(admin, &5000);
+    let holder = Address::generate(env);
+    let policy_id = create_paid_policy(env, client, &holder, 1000, 100);
+    let claim_id = Bytes::from_slice(env, b"claim-1");
+    client.submit_claim(&holder, &claim_id, &policy_id, &300, &evidence(env), &None);
+    client.add_claim_evidence(&claim_id, &evidence(env));
+    client.add_claim_evidence(&claim_id, &evidence(env));
+    claim_id
+}
+
+#[test]
+fn test_evidence_can_be_added_to_pending_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let claim_id = claim_with_three_evidence_items(&env, &client, &admin);
+
+    assert_eq!(client.get_claim(&claim_id).evidence.len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_evidence_beyond_cap_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let claim_id = claim_with_three_evidence_items(&env, &client, &admin);
+
+    client.add_claim_evidence(&claim_id, &evidence(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_evidence_after_processing_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 300);
+
+    client.add_claim_evidence(&claim_id, &evidence(&env));
+}
+
+/// Price oracle reporting a fixed market value of 1000
+#[contract]
+pub struct MockPriceOracle;
+
+#[contractimpl]
+impl MockPriceOracle {
+    pub fn market_value(_env: Env, _pool_data: Bytes) -> i128 {
+        1000
+    }
+}
+
+fn submit_market_loss_claim(env: &Env, client: &InsuranceContractClient, admin: &Address, claimed_value: i128) -> Bytes {
+    client.update_price_oracle(admin, &Some(env.register_contract(None, MockPriceOracle)));
+    client.fund_risk_pool(admin, &5000);
+    let holder = Address::generate(env);
+    let policy_id = create_paid_policy(env, client, &holder, 1000, 100);
+    let claim_id = Bytes::from_slice(env, b"claim-1");
+    let evidence = ClaimEvidence {
+        evidence_type: Symbol::new(env, "market_loss"),
+        market_value: Some(claimed_value),
+        ..evidence(env)
+    };
+    client.submit_claim(&holder, &claim_id, &policy_id, &300, &evidence, &None);
+    claim_id
+}
+
+#[test]
+fn test_truthful_market_loss_passes_oracle_check() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    // Within the default 5% tolerance of the oracle's 1000
+    let claim_id = submit_market_loss_claim(&env, &client, &admin, 1040);
+
+    assert!(!client.get_claim(&claim_id).oracle_flagged);
+}
+
+#[test]
+fn test_inflated_market_loss_is_flagged() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let claim_id = submit_market_loss_claim(&env, &client, &admin, 1500);
+
+    assert!(client.get_claim(&claim_id).oracle_flagged);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_inflated_market_loss_is_rejected_in_strict_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let mut config = client.get_config();
+    config.reject_oracle_mismatch = true;
+    client.update_config(&admin, &config);
+
+    submit_market_loss_claim(&env, &client, &admin, 1500);
+}
+
+#[test]
+fn test_transfer_policy_moves_it_between_holder_lists() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let holder = Address::generate(&env);
+    let new_holder = Address::generate(&env);
+    let policy_id = create_policy(&env, &client, b"policy-1", &holder, 1000, 100);
+    let kept_policy = create_policy(&env, &client, b"policy-2", &holder, 1000, 100);
+
+    client.transfer_policy(&policy_id, &new_holder);
+
+    assert_eq!(env.auths()[0].0, holder);
+    assert_eq!(client.get_policy(&policy_id).holder, new_holder);
+    assert_eq!(client.get_user_policies(&holder), vec![&env, kept_policy]);
+    assert_eq!(client.get_user_policies(&new_holder), vec![&env, policy_id]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_transfer_policy_with_pending_claim_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    client.submit_claim(&holder, &Bytes::from_slice(&env, b"claim-1"), &policy_id, &100, &evidence(&env), &None);
+
+    client.transfer_policy(&policy_id, &Address::generate(&env));
+}
+
+#[test]
+fn test_create_policy_receipt_matches_stored_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let holder = Address::generate(&env);
+    let policy_id = Bytes::from_slice(&env, b"policy-1");
+
+    let receipt = client.create_policy(&policy_id, &policy_params(&env, &holder, 1000, 100));
+
+    let policy = client.get_policy(&policy_id);
+    assert_eq!(receipt.policy_id, policy_id);
+    assert_eq!(receipt.premium, policy.premium);
+    assert_eq!(receipt.expiry_time, policy.expiry_time());
+    assert_eq!(receipt.effective_coverage, policy.effective_coverage(&env));
+}
+
+fn require_funded_pool(client: &InsuranceContractClient, admin: &Address) {
+    let mut config = client.get_config();
+    config.require_funded_pool = true;
+    client.update_config(admin, &config);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_strict_mode_rejects_policy_against_empty_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    require_funded_pool(&client, &admin);
+    assert!(!client.is_solvent_to_write());
+
+    create_policy(&env, &client, b"policy-1", &Address::generate(&env), 1000, 100);
+}
+
+#[test]
+fn test_strict_mode_accepts_policy_after_funding() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    require_funded_pool(&client, &admin);
+    client.fund_risk_pool(&admin, &5000);
+    assert!(client.is_solvent_to_write());
+
+    let policy_id = create_policy(&env, &client, b"policy-1", &Address::generate(&env), 1000, 100);
+
+    assert_eq!(client.get_policy(&policy_id).coverage_amount, 1000);
+}
+
+#[test]
+fn test_pool_below_constructor_minimum_is_not_solvent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let client = common::deploy_insurance(&env, &admin, &Address::generate(&env), 1000);
+
+    client.fund_risk_pool(&admin, &999);
+    assert!(!client.is_solvent_to_write());
+    client.fund_risk_pool(&admin, &1);
+    assert!(client.is_solvent_to_write());
+}
+
+#[test]
+fn test_pool_exposure_tracks_active_coverage_per_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let holder = Address::generate(&env);
+    let (pool_a, pool_b) = (Bytes::from_slice(&env, b"pool-a"), Bytes::from_slice(&env, b"pool-b"));
+    let policies = [(b"policy-1", &pool_a, 1000), (b"policy-2", &pool_a, 2000), (b"policy-3", &pool_b, 3000)];
+    for (policy_id, pool_id, coverage) in policies {
+        client.create_policy(
+            &Bytes::from_slice(&env, policy_id),
+            &CreatePolicyParams { pool_id: pool_id.clone(), ..policy_params(&env, &holder, coverage, coverage / 10) },
+        );
+    }
+    assert_eq!(client.get_pool_exposure(&pool_a), 3000);
+    assert_eq!(client.get_pool_exposure(&pool_b), 3000);
+
+    client.cancel_policy(&Bytes::from_slice(&env, b"policy-1"));
+    assert_eq!(client.get_pool_exposure(&pool_a), 2000);
+
+    let policy_3 = Bytes::from_slice(&env, b"policy-3");
+    let expired_at = client.get_policy(&policy_3).expiry_time() + client.get_config().renewal_grace_period;
+    env.ledger().with_mut(|ledger| ledger.timestamp = expired_at);
+    client.expire_policy(&policy_3);
+    assert_eq!(client.get_pool_exposure(&pool_a), 2000);
+    assert_eq!(client.get_pool_exposure(&pool_b), 0);
+}
+
+/// Submit a claim on a one-day policy and let the policy expire before processing
+fn claim_on_lapsed_policy(env: &Env, client: &InsuranceContractClient, admin: &Address) -> Bytes {
+    client.fund_risk_pool(admin, &5000);
+    let holder = Address::generate(env);
+    let policy_id = Bytes::from_slice(env, b"policy-1");
+    client.create_policy(&policy_id, &CreatePolicyParams { duration: 86400, ..policy_params(env, &holder, 1000, 100) });
+    client.pay_premium(&policy_id, &100);
+    let claim_id = Bytes::from_slice(env, b"claim-1");
+    client.submit_claim(&holder, &claim_id, &policy_id, &300, &evidence(env), &None);
+    advance_time(env, 86400);
+    claim_id
+}
+
+#[test]
+fn test_claim_on_policy_expired_before_processing_is_auto_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let claim_id = claim_on_lapsed_policy(&env, &client, &admin);
+
+    client.process_claim(&claim_id, &true, &admin, &Symbol::new(&env, "approved"), &None);
+
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::Rejected);
+    assert_eq!(claim.rejection_reason, RejectionReason::PolicyInactive);
+    assert_eq!(claim.reason, Symbol::new(&env, "policy_inactive"));
+    assert_eq!(client.get_rejection_stats().policy_inactive, 1);
+    assert_eq!(client.get_reserved_balance(), 0);
+}
+
+#[test]
+fn test_claim_on_lapsed_policy_can_be_approved_without_auto_rejection() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let mut config = client.get_config();
+    config.auto_reject_inactive_claims = false;
+    client.update_config(&admin, &config);
+    let claim_id = claim_on_lapsed_policy(&env, &client, &admin);
+
+    client.process_claim(&claim_id, &true, &admin, &Symbol::new(&env, "approved"), &None);
+
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::Approved);
+}
+
+#[test]
+fn test_funding_breakdown_tracks_each_source() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    client.fund_risk_pool_tagged(&admin, &3000, &FundSource::Grant);
+    client.fund_risk_pool_tagged(&admin, &1200, &FundSource::Yield);
+    client.fund_risk_pool_tagged(&admin, &800, &FundSource::Grant);
+
+    let breakdown = client.get_funding_breakdown();
+    assert_eq!(breakdown.grants, 3800);
+    assert_eq!(breakdown.yield_income, 1200);
+    assert_eq!((breakdown.premiums, breakdown.other), (0, 0));
+    assert_eq!(breakdown.total(), 5000);
+    assert_eq!(client.get_risk_pool_balance(), 5000);
+}
+
+/// Conversion rate source quoting two payout-token units per premium-token unit
+#[contract]
+pub struct MockConversionRate;
+
+#[contractimpl]
+impl MockConversionRate {
+    pub fn rate(_env: Env, _from: Address, _to: Address) -> i128 {
+        2 * RATE_SCALE
+    }
+}
+
+fn pay_claim_in_settlement_tokens(env: &Env, premium_token: &Address, payout_token: &Address) -> i128 {
+    let (client, admin) = setup(env);
+    let rate_source = env.register_contract(None, MockConversionRate);
+    client.update_settlement_tokens(&admin, premium_token, payout_token, &Some(rate_source));
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(env);
+    let policy_id = create_paid_policy(env, &client, &holder, 1000, 100);
+    let claim_id = approved_claim(env, &client, &admin, &holder, &policy_id, b"claim-1", 300);
+    advance_time(env, SETTLEMENT_DELAY);
+
+    client.pay_claim(&claim_id);
+
+    // The risk pool is charged in the premium token either way
+    assert_eq!(client.get_risk_pool_balance(), 4700);
+    claim_paid(env).1
+}
+
+#[test]
+fn test_payout_in_distinct_token_is_converted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (premium_token, payout_token) = (Address::generate(&env), Address::generate(&env));
+
+    let amount = pay_claim_in_settlement_tokens(&env, &premium_token, &payout_token);
+
+    assert_eq!(amount, 600);
+}
+
+#[test]
+fn test_payout_in_premium_token_is_unconverted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let token = Address::generate(&env);
+
+    let amount = pay_claim_in_settlement_tokens(&env, &token, &token);
+
+    assert_eq!(amount, 300);
+}
+
+fn submit_claim_on_new_policy(env: &Env, client: &InsuranceContractClient, policy_id: &[u8], claim_id: &[u8]) -> Bytes {
+    let holder = Address::generate(env);
+    let policy_id = create_policy(env, client, policy_id, &holder, 1000, 100);
+    client.pay_premium(&policy_id, &100);
+    let claim_id = Bytes::from_slice(env, claim_id);
+    client.submit_claim(&holder, &claim_id, &policy_id, &100, &evidence(env), &None);
+    claim_id
+}
+
+#[test]
+fn test_overdue_claims_exclude_fresh_and_processed_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let first = submit_claim_on_new_policy(&env, &client, b"policy-1", b"claim-1");
+    let second = submit_claim_on_new_policy(&env, &client, b"policy-2", b"claim-2");
+    advance_time(&env, client.get_config().claim_sla + 1);
+    submit_claim_on_new_policy(&env, &client, b"policy-3", b"claim-3");
+
+    assert_eq!(client.get_overdue_claims(&0, &10), vec![&env, first.clone(), second.clone()]);
+    assert_eq!(client.get_overdue_claims(&1, &1), vec![&env, second.clone()]);
+
+    client.process_claim(&first, &false, &admin, &Symbol::new(&env, "rejected"), &None);
+    assert_eq!(client.get_overdue_claims(&0, &10), vec![&env, second]);
+}
+
+#[test]
+fn test_project_payout_applies_coverage_and_deductible() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = Bytes::from_slice(&env, b"policy-1");
+    client.create_policy(&policy_id, &CreatePolicyParams { deductible: 100, ..policy_params(&env, &holder, 1000, 100) });
+    client.pay_premium(&policy_id, &100);
+
+    // Effective coverage is 900 after the 10% risk-score reduction
+    assert_eq!(client.project_payout(&policy_id, &500), 400);
+    assert_eq!(client.project_payout(&policy_id, &2000), 800);
+    assert_eq!(client.project_payout(&policy_id, &100), 0);
+    assert_eq!(client.project_payout(&policy_id, &50), 0);
+
+    // Earlier payouts reduce the remaining coverage
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 300);
+    advance_time(&env, SETTLEMENT_DELAY);
+    client.pay_claim(&claim_id);
+    assert_eq!(client.get_policy(&policy_id).claimed_amount, 200);
+    assert_eq!(client.project_payout(&policy_id, &2000), 600);
+}
+
+/// Pay a 500 claim while the pool only holds 200, leaving 300 unpaid
+fn partially_paid_claim(env: &Env, client: &InsuranceContractClient, admin: &Address) -> (Bytes, Bytes) {
+    let mut config = client.get_config();
+    config.partial_payout_enabled = true;
+    client.update_config(admin, &config);
+    client.fund_risk_pool(admin, &5000);
+    let holder = Address::generate(env);
+    let policy_id = create_paid_policy(env, client, &holder, 1000, 100);
+    let claim_id = approved_claim(env, client, admin, &holder, &policy_id, b"claim-1", 500);
+    advance_time(env, SETTLEMENT_DELAY);
+
+    // A loss on the pool's assets after approval leaves it short of this claim
+    client.record_risk_pool_loss(admin, &4800);
+    client.pay_claim(&claim_id);
+    (policy_id, claim_id)
+}
+
+#[test]
+fn test_claim_remainder_settles_partially_paid_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let (policy_id, claim_id) = partially_paid_claim(&env, &client, &admin);
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::PartiallyPaid);
+    assert_eq!(claim.unpaid_remainder, 300);
+
+    client.fund_risk_pool(&admin, &100);
+    assert_eq!(client.claim_remainder(&claim_id), 100);
+    assert_eq!(client.get_claim(&claim_id).unpaid_remainder, 200);
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::PartiallyPaid);
+
+    client.fund_risk_pool(&admin, &1000);
+    assert_eq!(client.claim_remainder(&claim_id), 200);
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::Paid);
+    assert_eq!(claim.unpaid_remainder, 0);
+    assert_eq!(client.get_policy(&policy_id).claimed_amount, 500);
+    assert_eq!(client.get_risk_pool_balance(), 800);
+    assert_eq!(client.get_reserved_balance(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_claim_remainder_from_empty_pool_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let (_, claim_id) = partially_paid_claim(&env, &client, &admin);
+
+    client.claim_remainder(&claim_id);
+}
+
+fn pay_claim_under_payout_cap(env: &Env, absolute_payout_cap: i128) -> (InsuranceContractClient<'_>, Bytes) {
+    let (client, admin) = setup(env);
+    let mut config = client.get_config();
+    config.absolute_payout_cap = absolute_payout_cap;
+    client.update_config(&admin, &config);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(env);
+    let policy_id = create_paid_policy(env, &client, &holder, 1000, 100);
+    let claim_id = approved_claim(env, &client, &admin, &holder, &policy_id, b"claim-1", 500);
+    advance_time(env, SETTLEMENT_DELAY);
+
+    client.pay_claim(&claim_id);
+    (client, policy_id)
+}
+
+#[test]
+fn test_payout_is_clamped_to_absolute_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // The cap sits below the policy's 900 effective coverage
+    let (client, policy_id) = pay_claim_under_payout_cap(&env, 300);
+
+    assert_eq!(claim_paid(&env).1, 300);
+    assert_eq!(client.get_policy(&policy_id).claimed_amount, 300);
+    assert_eq!(client.get_risk_pool_balance(), 4700);
+}
+
+#[test]
+fn test_zero_payout_cap_is_disabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, policy_id) = pay_claim_under_payout_cap(&env, 0);
+
+    assert_eq!(claim_paid(&env).1, 500);
+    assert_eq!(client.get_policy(&policy_id).claimed_amount, 500);
+}
+
+#[test]
+fn test_holder_and_beneficiary_can_submit_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let (holder, beneficiary) = (Address::generate(&env), Address::generate(&env));
+    let policy_id = Bytes::from_slice(&env, b"policy-1");
+    client.create_policy(
+        &policy_id,
+        &CreatePolicyParams { beneficiary: Some(beneficiary.clone()), ..policy_params(&env, &holder, 1000, 100) },
+    );
+    client.pay_premium(&policy_id, &100);
+
+    let holder_claim = Bytes::from_slice(&env, b"claim-1");
+    client.submit_claim(&holder, &holder_claim, &policy_id, &100, &evidence(&env), &None);
+    assert_eq!(env.auths()[0].0, holder);
+    advance_time(&env, client.get_config().claim_cooldown);
+    let beneficiary_claim = Bytes::from_slice(&env, b"claim-2");
+    client.submit_claim(&beneficiary, &beneficiary_claim, &policy_id, &100, &evidence(&env), &None);
+    assert_eq!(env.auths()[0].0, beneficiary);
+
+    assert_eq!(client.get_claim(&holder_claim).claimant, holder);
+    assert_eq!(client.get_claim(&beneficiary_claim).claimant, beneficiary);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_third_party_cannot_claim_on_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let policy_id = create_paid_policy(&env, &client, &Address::generate(&env), 1000, 100);
+
+    let claim_id = Bytes::from_slice(&env, b"claim-1");
+    client.submit_claim(&Address::generate(&env), &claim_id, &policy_id, &100, &evidence(&env), &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_submit_claim_requires_claimant_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let policy_id = create_paid_policy(&env, &client, &holder, 1000, 100);
+    env.set_auths(&[]);
+
+    client.submit_claim(&holder, &Bytes::from_slice(&env, b"claim-1"), &policy_id, &100, &evidence(&env), &None);
+}
+
+/// Cap `pool-a` at 2000 and write coverage up to the cap
+fn fill_capped_pool(env: &Env, client: &InsuranceContractClient, admin: &Address, holder: &Address) -> Bytes {
+    let pool_id = Bytes::from_slice(env, b"pool-a");
+    client.update_pool_coverage_cap(admin, &pool_id, &2000);
+    for policy_id in [b"policy-1", b"policy-2"] {
+        client.create_policy(
+            &Bytes::from_slice(env, policy_id),
+            &CreatePolicyParams { pool_id: pool_id.clone(), ..policy_params(env, holder, 1000, 100) },
+        );
+    }
+    assert_eq!(client.get_pool_exposure(&pool_id), 2000);
+    pool_id
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_policy_exceeding_pool_cap_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let holder = Address::generate(&env);
+    let pool_id = fill_capped_pool(&env, &client, &admin, &holder);
+
+    client.create_policy(
+        &Bytes::from_slice(&env, b"policy-3"),
+        &CreatePolicyParams { pool_id, ..policy_params(&env, &holder, 1000, 100) },
+    );
+}
+
+#[test]
+fn test_other_pool_accepts_coverage_when_one_is_full() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let holder = Address::generate(&env);
+    fill_capped_pool(&env, &client, &admin, &holder);
+    let other_pool = Bytes::from_slice(&env, b"pool-b");
+
+    client.create_policy(
+        &Bytes::from_slice(&env, b"policy-3"),
+        &CreatePolicyParams { pool_id: other_pool.clone(), ..policy_params(&env, &holder, 1000, 100) },
+    );
+
+    assert_eq!(client.get_pool_exposure(&other_pool), 1000);
+}
+
+#[test]
+fn test_rebuild_user_index_repairs_corrupted_list() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let (holder, other) = (Address::generate(&env), Address::generate(&env));
+    let first = create_policy(&env, &client, b"policy-1", &holder, 1000, 100);
+    create_policy(&env, &client, b"policy-2", &other, 1000, 100);
+    let third = create_policy(&env, &client, b"policy-3", &holder, 1000, 100);
+
+    // Drop one owned policy and add a stray one to the holder's index
+    env.as_contract(&client.address, || {
+        let corrupted = vec![&env, Bytes::from_slice(&env, b"policy-2"), third.clone()];
+        env.storage().instance().set(&(Symbol::new(&env, "user_policies"), holder.clone()), &corrupted);
+    });
+
+    let rebuilt = client.rebuild_user_index(&admin, &holder);
+
+    assert_eq!(rebuilt, vec![&env, first, third]);
+    assert_eq!(client.get_user_policies(&holder), rebuilt);
+}
+
+#[test]
+fn test_declining_coverage_scales_with_remaining_term() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let holder = Address::generate(&env);
+    let term = 100 * 86400;
+    let declining = Bytes::from_slice(&env, b"declining");
+    client.create_policy(
+        &declining,
+        &CreatePolicyParams { duration: term, declining: true, ..policy_params(&env, &holder, 1000, 100) },
+    );
+    let level = create_policy(&env, &client, b"level", &holder, 1000, 100);
+
+    // Effective coverage starts at 900 after the 10% risk-score reduction
+    for (elapsed_pct, expected) in [(0, 900), (50, 450), (90, 90)] {
+        env.ledger().with_mut(|ledger| ledger.timestamp = term * elapsed_pct / 100);
+        assert_eq!(client.project_payout(&declining, &i128::MAX), expected);
+        assert_eq!(client.project_payout(&level, &i128::MAX), 900);
+    }
+}
+
+#[test]
+fn test_declining_claim_pays_coverage_from_submission() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(&env);
+    let term = 100 * 86400;
+    let policy_id = Bytes::from_slice(&env, b"declining");
+    client.create_policy(
+        &policy_id,
+        &CreatePolicyParams { duration: term, declining: true, ..policy_params(&env, &holder, 1000, 100) },
+    );
+    client.pay_premium(&policy_id, &100);
+
+    // Submitted halfway through the term, while 450 of coverage remains
+    advance_time(&env, term / 2);
+    let claim_id = approved_claim(&env, &client, &admin, &holder, &policy_id, b"claim-1", 450);
+    assert_eq!(client.get_claim(&claim_id).effective_coverage, 450);
+
+    // Paying late in the term doesn't shrink the payout further
+    advance_time(&env, term * 4 / 10);
+    let pool_before = client.get_risk_pool_balance();
+    client.pay_claim(&claim_id);
+
+    assert_eq!(pool_before - client.get_risk_pool_balance(), 450);
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::Paid);
+}
+
+/// Write a 100-day policy with a 1000 premium under a 14-day cooling-off period
+fn policy_with_free_cancel_window(env: &Env) -> (InsuranceContractClient<'_>, Bytes) {
+    let (client, admin) = setup(env);
+    let mut config = client.get_config();
+    config.free_cancel_window = 14 * 86400;
+    client.update_config(&admin, &config);
+    let policy_id = Bytes::from_slice(env, b"policy-1");
+    client.create_policy(
+        &policy_id,
+        &CreatePolicyParams { duration: 100 * 86400, ..policy_params(env, &Address::generate(env), 10000, 1000) },
+    );
+    client.pay_premium(&policy_id, &1000);
+    (client, policy_id)
+}
+
+#[test]
+fn test_cancel_inside_free_window_refunds_full_premium() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, policy_id) = policy_with_free_cancel_window(&env);
+    advance_time(&env, 14 * 86400 - 1);
+
+    assert_eq!(client.cancel_policy(&policy_id), 1000);
+    assert_eq!(client.get_policy(&policy_id).status, PolicyStatus::Cancelled);
+}
+
+#[test]
+fn test_cancel_after_free_window_is_pro_rated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, policy_id) = policy_with_free_cancel_window(&env);
+    advance_time(&env, 14 * 86400);
+
+    assert_eq!(client.cancel_policy(&policy_id), 860);
+}
+
+#[test]
+fn test_bundle_splits_discount_in_proportion_to_premiums() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let mut config = client.get_config();
+    config.bundle_discount_bps = 1000;
+    client.update_config(&admin, &config);
+    let holder = Address::generate(&env);
+    let bundle = vec![
+        &env,
+        policy_params(&env, &holder, 1000, 100),
+        policy_params(&env, &holder, 2000, 200),
+        policy_params(&env, &holder, 3000, 300),
+    ];
+
+    let receipts = client.create_policies_bundle(&bundle);
+
+    // 10% of the combined 600 premium comes off, split 10/20/30
+    let premiums: std::vec::Vec<i128> = receipts.iter().map(|receipt| receipt.premium).collect();
+    assert_eq!(premiums, [90, 180, 270]);
+    assert_eq!(client.get_user_policies(&holder).len(), 3);
+    assert_eq!(client.get_policy(&receipts.get(2).unwrap().policy_id).premium, 270);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_bundle_with_invalid_policy_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let holder = Address::generate(&env);
+    let bundle = vec![
+        &env,
+        policy_params(&env, &holder, 1000, 100),
+        CreatePolicyParams { duration: 3600, ..policy_params(&env, &holder, 1000, 100) },
+    ];
+
+    client.create_policies_bundle(&bundle);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_bundle_requires_holder_auth() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let holder = Address::generate(&env);
+    let bundle = vec![&env, policy_params(&env, &holder, 1000, 100), policy_params(&env, &holder, 2000, 200)];
+
+    client.create_policies_bundle(&bundle);
+}
+
+/// Write a paid policy under a one-week claim waiting period
+fn policy_with_waiting_period(env: &Env) -> (InsuranceContractClient<'_>, Address, Bytes) {
+    let (client, admin) = setup(env);
+    let mut config = client.get_config();
+    config.claim_waiting_period = 7 * 86400;
+    client.update_config(&admin, &config);
+    client.fund_risk_pool(&admin, &5000);
+    let holder = Address::generate(env);
+    let policy_id = create_paid_policy(env, &client, &holder, 1000, 100);
+    (client, holder, policy_id)
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_claim_during_waiting_period_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, holder, policy_id) = policy_with_waiting_period(&env);
+    advance_time(&env, 7 * 86400 - 1);
+
+    client.submit_claim(&holder, &Bytes::from_slice(&env, b"claim-1"), &policy_id, &100, &evidence(&env), &None);
+}
+
+#[test]
+fn test_claim_after_waiting_period_is_accepted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, holder, policy_id) = policy_with_waiting_period(&env);
+    advance_time(&env, 7 * 86400);
+    let claim_id = Bytes::from_slice(&env, b"claim-1");
+
+    client.submit_claim(&holder, &claim_id, &policy_id, &100, &evidence(&env), &None);
+
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::Pending);
+}
+
+/// Cap pending claims at half of a 1000 pool and fill the cap with five 100 claims
+fn fill_pending_exposure(env: &Env) -> InsuranceContractClient<'_> {
+    let (client, admin) = setup(env);
+    let mut config = client.get_config();
+    config.max_pending_exposure_bps = 5000;
+    client.update_config(&admin, &config);
+    client.fund_risk_pool(&admin, &1000);
+    let ids: [(&[u8], &[u8]); 5] = [
+        (b"policy-1", b"claim-1"),
+        (b"policy-2", b"claim-2"),
+        (b"policy-3", b"claim-3"),
+        (b"policy-4", b"claim-4"),
+        (b"policy-5", b"claim-5"),
+    ];
+    for (policy_id, claim_id) in ids {
+        submit_claim_on_new_policy(env, &client, policy_id, claim_id);
+    }
+    client
+}
+
+#[test]
+fn test_pending_claims_fill_exposure_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = fill_pending_exposure(&env);
+
+    assert_eq!(client.get_reserved_balance(), 500);
+    assert_eq!(client.get_available_risk_pool_balance(), 500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_claim_breaching_exposure_limit_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = fill_pending_exposure(&env);
+
+    // The pool could still reserve it, but pending claims would exceed half the pool
+    submit_claim_on_new_policy(&env, &client, b"policy-6", b"claim-6");
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_constructor_cannot_run_twice() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let attacker = Address::generate(&env);
+
+    env.as_contract(&client.address, || {
+        InsuranceContract::__constructor(env.clone(), attacker.clone(), attacker.clone(), 0, 0);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_get_owner_before_construction_returns_not_initialized() {
+    let env = Env::default();
+    let client = InsuranceContractClient::new(&env, &env.register_contract(None, InsuranceContract));
+
+    client.get_owner();
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_get_missing_claim_returns_not_found() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+
+    client.get_claim(&Bytes::from_slice(&env, b"missing"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_record_risk_pool_loss_rejects_more_than_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.fund_risk_pool(&admin, &1000);
+
+    client.record_risk_pool_loss(&admin, &1001);
+}
@@ -0,0 +1,348 @@
+//! Integration tests for the shared contract utilities
+
+use contracts::shared::{
+    generate_id, is_zero_address, next_id_nonce, require_nonzero_address, saturating_u32,
+    split_by_percentage, to_base_units, to_whole_units, AccessControl, ContractError, InitGuard,
+    SignatureNonces, SystemGuard, CONTRACT_VERSION, MIN_ADMINS, ZERO_ACCOUNT, ZERO_CONTRACT,
+};
+use contracts::HelloContract;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{vec, Address, Bytes, BytesN, Env, Error, String, Symbol};
+
+#[test]
+fn test_init_guard_marks_initialized() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HelloContract);
+
+    env.as_contract(&contract_id, || {
+        assert!(!InitGuard::is_initialized(&env));
+        InitGuard::initialize_once(&env);
+        assert!(InitGuard::is_initialized(&env));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_init_guard_rejects_second_initialization() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HelloContract);
+
+    env.as_contract(&contract_id, || {
+        InitGuard::initialize_once(&env);
+        InitGuard::initialize_once(&env);
+    });
+}
+
+#[test]
+fn test_system_guard_pause_and_unpause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, HelloContract);
+    let guardian = Address::generate(&env);
+
+    // Each guardian call runs in its own frame, as separate invocations would
+    env.as_contract(&contract_id, || {
+        SystemGuard::set_guardian(&env, &guardian);
+        assert!(!SystemGuard::is_paused(&env));
+    });
+
+    env.as_contract(&contract_id, || {
+        SystemGuard::set_paused(&env, guardian.clone(), true);
+        assert!(SystemGuard::is_paused(&env));
+    });
+
+    env.as_contract(&contract_id, || {
+        SystemGuard::set_paused(&env, guardian.clone(), false);
+        assert!(!SystemGuard::is_paused(&env));
+        SystemGuard::require_not_paused(&env);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_system_guard_blocks_when_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, HelloContract);
+    let guardian = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        SystemGuard::set_guardian(&env, &guardian);
+        SystemGuard::set_paused(&env, guardian.clone(), true);
+        SystemGuard::require_not_paused(&env);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_system_guard_rejects_non_guardian() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, HelloContract);
+    let guardian = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        SystemGuard::set_guardian(&env, &guardian);
+        SystemGuard::set_paused(&env, other, true);
+    });
+}
+
+#[test]
+fn test_saturating_u32_passes_through_in_range_values() {
+    assert_eq!(saturating_u32(0), 0);
+    assert_eq!(saturating_u32(250), 250);
+    assert_eq!(saturating_u32(u32::MAX as i128), u32::MAX);
+}
+
+#[test]
+fn test_saturating_u32_clamps_large_values_instead_of_wrapping() {
+    // A premium of 1e30 on coverage of 1 yields a basis-point ratio far above u32::MAX;
+    // a plain `as u32` cast would wrap this to an arbitrary small number.
+    let premium: i128 = 1_000_000_000_000_000_000_000_000_000_000;
+    let coverage: i128 = 1;
+    let ratio = premium.saturating_mul(10000) / coverage;
+    assert_eq!(saturating_u32(ratio), u32::MAX);
+    assert_eq!(saturating_u32((u32::MAX as i128) + 1), u32::MAX);
+}
+
+#[test]
+fn test_saturating_u32_clamps_negative_values_to_zero() {
+    assert_eq!(saturating_u32(-1), 0);
+    assert_eq!(saturating_u32(i128::MIN), 0);
+}
+
+#[test]
+fn test_to_base_units_scales_by_decimals() {
+    // 100 whole tokens of a 7-decimal asset vs a 2-decimal asset
+    assert_eq!(to_base_units(100, 7), 1_000_000_000);
+    assert_eq!(to_base_units(100, 2), 10_000);
+    assert_eq!(to_base_units(100, 0), 100);
+}
+
+#[test]
+fn test_to_whole_units_round_trips_and_truncates() {
+    assert_eq!(to_whole_units(to_base_units(42, 7), 7), 42);
+    assert_eq!(to_whole_units(199, 2), 1);
+}
+
+#[test]
+fn test_coverage_bounds_depend_on_decimal_scale() {
+    // A 10,000-token cap admits 5,000 tokens at 7 decimals but not 5,000 tokens
+    // mistakenly expressed at 7 decimals against a 2-decimal cap
+    let coverage = to_base_units(5_000, 7);
+    assert!(coverage <= to_base_units(10_000, 7));
+    assert!(coverage > to_base_units(10_000, 2));
+}
+
+#[test]
+fn test_to_base_units_saturates_instead_of_overflowing() {
+    assert_eq!(to_base_units(i128::MAX, 7), i128::MAX);
+}
+
+#[test]
+fn test_not_initialized_error_code_is_stable() {
+    assert_eq!(
+        Error::from(ContractError::NotInitialized),
+        Error::from_contract_error(17)
+    );
+}
+
+#[test]
+fn test_split_by_percentage_sums_to_amount() {
+    let mut amount: i128 = 0;
+    while amount < 5_000 {
+        for percentage in 0..=100u32 {
+            let (share, remainder) = split_by_percentage(amount, percentage);
+            assert_eq!(share + remainder, amount);
+            assert_eq!(share, amount * percentage as i128 / 100);
+        }
+        amount += 7;
+    }
+}
+
+#[test]
+fn test_split_by_percentage_gives_rounding_remainder_to_second_part() {
+    // 30% of 101 truncates to 30, leaving the lost unit in the remainder
+    assert_eq!(split_by_percentage(101, 30), (30, 71));
+    assert_eq!(split_by_percentage(1, 50), (0, 1));
+}
+
+#[test]
+fn test_three_way_split_sums_to_principal() {
+    // Mirrors treasury rebalancing: insurance and operational shares, emergency gets the rest
+    for principal in [1i128, 3, 99, 101, 1_000_003, 7_777_777_777] {
+        let (insurance, rest) = split_by_percentage(principal, 33);
+        let operational = split_by_percentage(principal, 33).0;
+        let emergency = rest - operational;
+        assert_eq!(insurance + operational + emergency, principal);
+        assert!(emergency >= 0);
+    }
+}
+
+#[test]
+fn test_zero_addresses_are_detected() {
+    let env = Env::default();
+    let zero_account = Address::from_string(&String::from_str(&env, ZERO_ACCOUNT));
+    let zero_contract = Address::from_string(&String::from_str(&env, ZERO_CONTRACT));
+
+    assert!(is_zero_address(&env, &zero_account));
+    assert!(is_zero_address(&env, &zero_contract));
+    assert!(!is_zero_address(&env, &Address::generate(&env)));
+}
+
+#[test]
+fn test_require_nonzero_address_accepts_regular_address() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HelloContract);
+
+    env.as_contract(&contract_id, || {
+        require_nonzero_address(&env, &Address::generate(&env));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_require_nonzero_address_rejects_zero_account() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HelloContract);
+
+    env.as_contract(&contract_id, || {
+        let zero_account = Address::from_string(&String::from_str(&env, ZERO_ACCOUNT));
+        require_nonzero_address(&env, &zero_account);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_require_nonzero_address_rejects_zero_contract() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HelloContract);
+
+    env.as_contract(&contract_id, || {
+        let zero_contract = Address::from_string(&String::from_str(&env, ZERO_CONTRACT));
+        require_nonzero_address(&env, &zero_contract);
+    });
+}
+
+#[test]
+fn test_revoke_role_allows_removal_down_to_floor() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HelloContract);
+    let admin = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    let mut admins = vec![&env, admin.clone(), second.clone(), third.clone()];
+
+    env.as_contract(&contract_id, || {
+        AccessControl::revoke_role(&env, &mut admins, admin.clone(), third);
+        AccessControl::revoke_role(&env, &mut admins, admin.clone(), second);
+    });
+
+    assert_eq!(admins.len(), MIN_ADMINS);
+    assert_eq!(admins, vec![&env, admin]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_revoke_role_rejects_removing_last_admin() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HelloContract);
+    let admin = Address::generate(&env);
+    let mut admins = vec![&env, admin.clone()];
+
+    env.as_contract(&contract_id, || {
+        AccessControl::revoke_role(&env, &mut admins, admin.clone(), admin);
+    });
+}
+
+#[test]
+fn test_revoke_role_ignores_non_members_at_floor() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HelloContract);
+    let admin = Address::generate(&env);
+    let mut admins = vec![&env, admin.clone()];
+
+    env.as_contract(&contract_id, || {
+        AccessControl::revoke_role(&env, &mut admins, admin.clone(), Address::generate(&env));
+    });
+
+    assert_eq!(admins.len(), 1);
+}
+
+#[test]
+fn test_generate_id_differs_by_nonce_and_is_stable() {
+    let env = Env::default();
+    let caller = Address::generate(&env);
+    let prefix = Symbol::new(&env, "policy");
+
+    let first = generate_id(&env, prefix.clone(), &caller, 0);
+    let second = generate_id(&env, prefix.clone(), &caller, 1);
+
+    assert_ne!(first, second);
+    assert_eq!(first.len(), 32);
+    assert_eq!(generate_id(&env, prefix.clone(), &caller, 0), first);
+    assert_ne!(generate_id(&env, Symbol::new(&env, "deposit"), &caller, 0), first);
+    assert_ne!(generate_id(&env, prefix, &Address::generate(&env), 0), first);
+}
+
+#[test]
+fn test_next_id_nonce_increments() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HelloContract);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(next_id_nonce(&env), 0);
+        assert_eq!(next_id_nonce(&env), 1);
+        assert_eq!(next_id_nonce(&env), 2);
+    });
+}
+
+#[test]
+fn test_contract_version_is_semantic() {
+    let parts: Vec<&str> = CONTRACT_VERSION.split('.').collect();
+
+    assert_eq!(CONTRACT_VERSION, env!("CARGO_PKG_VERSION"));
+    assert_eq!(parts.len(), 3);
+    assert!(parts.iter().all(|part| part.parse::<u32>().is_ok()));
+}
+
+#[test]
+fn test_signature_nonce_is_single_use() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HelloContract);
+    let signer = BytesN::from_array(&env, &[7; 32]);
+    let other = BytesN::from_array(&env, &[8; 32]);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(SignatureNonces::get(&env, &signer), 0);
+        SignatureNonces::consume(&env, &signer, 0);
+        SignatureNonces::consume(&env, &signer, 1);
+
+        assert_eq!(SignatureNonces::get(&env, &signer), 2);
+        assert_eq!(SignatureNonces::get(&env, &other), 0);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_signature_nonce_rejects_replay() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HelloContract);
+    let signer = BytesN::from_array(&env, &[7; 32]);
+
+    env.as_contract(&contract_id, || {
+        SignatureNonces::consume(&env, &signer, 0);
+        SignatureNonces::consume(&env, &signer, 0);
+    });
+}
+
+#[test]
+fn test_signed_payload_appends_nonce() {
+    let env = Env::default();
+    let message = Bytes::from_array(&env, &[1, 2, 3]);
+
+    let payload = SignatureNonces::signed_payload(&env, &message, 5);
+
+    assert_eq!(payload, Bytes::from_array(&env, &[1, 2, 3, 0, 0, 0, 0, 0, 0, 0, 5]));
+}
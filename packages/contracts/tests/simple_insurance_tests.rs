@@ -0,0 +1,76 @@
+//! Integration tests for the SimpleInsurance contract
+
+use contracts::simple_insurance::{SimpleInsurance, SimpleInsuranceClient};
+use soroban_sdk::testutils::{Address as _, Events as _};
+use soroban_sdk::{Address, Bytes, Env, IntoVal, Symbol};
+
+fn setup(env: &Env) -> SimpleInsuranceClient<'_> {
+    let contract_id = env.register_contract(None, SimpleInsurance);
+    SimpleInsuranceClient::new(env, &contract_id)
+}
+
+#[test]
+fn test_create_policy_emits_event() {
+    let env = Env::default();
+    let client = setup(&env);
+    let holder = Address::generate(&env);
+
+    let policy_id = client.create_policy(&holder, &1000);
+
+    let (contract, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(contract, client.address);
+    assert_eq!(topics, (Symbol::new(&env, "policy_created"), policy_id.clone()).into_val(&env));
+    let (event_holder, event_amount): (Address, i128) = data.into_val(&env);
+    assert_eq!(event_holder, holder);
+    assert_eq!(event_amount, 1000);
+}
+
+#[test]
+fn test_deactivate_policy_emits_event() {
+    let env = Env::default();
+    let client = setup(&env);
+    let holder = Address::generate(&env);
+
+    let policy_id = client.create_policy(&holder, &1000);
+    client.deactivate_policy(&policy_id);
+
+    let (contract, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(contract, client.address);
+    assert_eq!(topics, (Symbol::new(&env, "policy_deactivated"), policy_id.clone()).into_val(&env));
+    let (event_holder, event_amount): (Address, i128) = data.into_val(&env);
+    assert_eq!(event_holder, holder);
+    assert_eq!(event_amount, 1000);
+    assert!(!client.get_policy(&policy_id).active);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_get_missing_policy_returns_policy_not_found() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    client.get_policy(&Bytes::from_array(&env, &[0xff; 32]));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_create_policy_rejects_non_positive_amount() {
+    let env = Env::default();
+    let client = setup(&env);
+    let holder = Address::generate(&env);
+
+    client.create_policy(&holder, &0);
+}
+
+#[test]
+fn test_policies_created_in_same_ledger_get_distinct_ids() {
+    let env = Env::default();
+    let client = setup(&env);
+    let holder = Address::generate(&env);
+
+    let first = client.create_policy(&holder, &1000);
+    let second = client.create_policy(&holder, &1000);
+
+    assert_ne!(first, second);
+    assert_eq!(client.get_user_policies(&holder).len(), 2);
+}
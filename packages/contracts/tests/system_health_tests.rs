@@ -0,0 +1,76 @@
+//! Cross-contract tests for the insurance `system_health` aggregation
+
+mod common;
+
+use contracts::insurance::InsuranceContractClient;
+use contracts::treasury::TreasuryClient;
+use contracts::yield_aggregator::{DepositParams, YieldAggregatorClient};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{vec, Address, Bytes, Env, Symbol};
+
+struct Stack<'a> {
+    admin: Address,
+    insurance: InsuranceContractClient<'a>,
+    treasury: TreasuryClient<'a>,
+    yield_aggregator: YieldAggregatorClient<'a>,
+}
+
+fn deploy(env: &Env) -> Stack<'_> {
+    let admin = Address::generate(env);
+    let guardian = Address::generate(env);
+    let insurance = common::deploy_insurance(env, &admin, &guardian, 0);
+    let treasury = common::deploy_treasury(env, &admin, vec![env, admin.clone()], &guardian);
+    let yield_aggregator =
+        common::deploy_yield_aggregator(env, &admin, &insurance.address, &treasury.address, &guardian);
+
+    Stack {
+        admin,
+        insurance,
+        treasury,
+        yield_aggregator,
+    }
+}
+
+#[test]
+fn test_system_health_without_references() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let stack = deploy(&env);
+    stack.insurance.fund_risk_pool(&stack.admin, &1000);
+
+    let health = stack.insurance.system_health();
+
+    assert_eq!(health.risk_pool_balance, 1000);
+    assert!(health.solvent);
+    assert_eq!(health.treasury_balance, None);
+    assert_eq!(health.yield_tvl, None);
+}
+
+#[test]
+fn test_system_health_aggregates_treasury_and_yield_balances() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let stack = deploy(&env);
+    stack.insurance.fund_risk_pool(&stack.admin, &1000);
+    stack.treasury.add_funds(&stack.admin, &2500, &Symbol::new(&env, "seed"));
+    stack.yield_aggregator.deposit(
+        &Bytes::from_slice(&env, b"deposit-1"),
+        &DepositParams {
+            depositor: Address::generate(&env),
+            amount: 4000,
+            pool_id: Bytes::from_slice(&env, b"pool"),
+            allocate_to_insurance: true,
+            custom_insurance_percentage: None,
+            lock_duration: 0,
+            referrer: None,
+        },
+    );
+    stack.insurance.update_treasury(&stack.admin, &Some(stack.treasury.address.clone()));
+    stack.insurance.update_yield_aggregator(&stack.admin, &Some(stack.yield_aggregator.address.clone()));
+
+    let health = stack.insurance.system_health();
+
+    assert_eq!(health.risk_pool_balance, 1000);
+    assert_eq!(health.treasury_balance, Some(2500));
+    assert_eq!(health.yield_tvl, Some(4000));
+}
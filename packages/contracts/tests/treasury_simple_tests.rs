@@ -0,0 +1,101 @@
+//! Integration tests for the simple Treasury contract
+
+use contracts::treasury_simple::{Treasury, TreasuryClient, TransferStatus};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{vec, Address, Bytes, Env, Symbol, Vec};
+
+fn setup(env: &Env) -> TreasuryClient<'_> {
+    let contract_id = env.register_contract(None, Treasury);
+    TreasuryClient::new(env, &contract_id)
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_create_transfer_rejects_non_positive_amount() {
+    let env = Env::default();
+    let client = setup(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    client.create_transfer(&from, &to, &0, &Symbol::new(&env, "payroll"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_add_funds_rejects_non_positive_amount() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    client.add_funds(&-5);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_get_missing_transfer_returns_not_found() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    client.get_transfer(&Bytes::from_array(&env, &[0xff; 32]));
+}
+
+fn create_at_sequence(
+    env: &Env,
+    client: &TreasuryClient<'_>,
+    sequence: u32,
+    from: &Address,
+    to: &Address,
+) -> Bytes {
+    env.ledger().with_mut(|ledger| ledger.sequence_number = sequence);
+    client.create_transfer(from, to, &100, &Symbol::new(env, "payroll"))
+}
+
+#[test]
+fn test_pending_index_tracks_status_changes() {
+    let env = Env::default();
+    let client = setup(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    let mut ids = Vec::new(&env);
+    for sequence in 1..=10 {
+        ids.push_back(create_at_sequence(&env, &client, sequence, &from, &to));
+    }
+    assert_eq!(client.get_pending_transfers().len(), 10);
+
+    client.approve_transfer(&ids.get(0).unwrap());
+    client.reject_transfer(&ids.get(1).unwrap());
+
+    let pending = client.get_pending_transfers();
+    assert_eq!(pending.len(), 8);
+    assert!(!pending.contains(ids.get(0).unwrap()));
+    assert!(!pending.contains(ids.get(1).unwrap()));
+    assert!(pending.contains(ids.get(2).unwrap()));
+}
+
+#[test]
+fn test_user_index_returns_only_that_users_transfers() {
+    let env = Env::default();
+    let client = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    let alice_to_bob = create_at_sequence(&env, &client, 1, &alice, &bob);
+    let bob_to_carol = create_at_sequence(&env, &client, 2, &bob, &carol);
+    let carol_to_alice = create_at_sequence(&env, &client, 3, &carol, &alice);
+    for sequence in 4..=12 {
+        create_at_sequence(&env, &client, sequence, &carol, &carol);
+    }
+
+    let alice_transfers = client.get_user_transfers(&alice, &None);
+    assert_eq!(alice_transfers, vec![&env, alice_to_bob.clone(), carol_to_alice.clone()]);
+
+    let bob_transfers = client.get_user_transfers(&bob, &None);
+    assert_eq!(bob_transfers, vec![&env, alice_to_bob.clone(), bob_to_carol]);
+
+    client.approve_transfer(&alice_to_bob);
+    let alice_approved = client.get_user_transfers(&alice, &Some(TransferStatus::Approved));
+    assert_eq!(alice_approved, vec![&env, alice_to_bob]);
+    let alice_pending = client.get_user_transfers(&alice, &Some(TransferStatus::Pending));
+    assert_eq!(alice_pending, vec![&env, carol_to_alice]);
+}
@@ -0,0 +1,877 @@
+//! Integration tests for the full Treasury contract
+
+mod common;
+
+use contracts::shared::Role;
+use contracts::treasury::{
+    ApprovalTier, FundAllocation, TransferCategory, TransferParams, TransferStatus, Treasury, TreasuryClient,
+};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{token, vec, Address, Bytes, Env, Symbol};
+
+fn setup(env: &Env) -> (TreasuryClient<'_>, Address) {
+    let owner = Address::generate(env);
+    let admins = vec![env, owner.clone(), Address::generate(env), Address::generate(env)];
+    (common::deploy_treasury(env, &owner, admins, &Address::generate(env)), owner)
+}
+
+fn transfer_params(env: &Env, amount: i128, is_emergency: bool) -> TransferParams {
+    TransferParams {
+        to: Address::generate(env),
+        amount,
+        reason: Symbol::new(env, "ops"),
+        category: TransferCategory::Ops,
+        asset: None,
+        required_approvals: None,
+        is_emergency,
+    }
+}
+
+#[test]
+fn test_owner_update_requires_owner_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+
+    client.update_min_transfer_amount(&owner, &50);
+
+    assert_eq!(env.auths()[0].0, owner);
+    assert_eq!(client.get_min_transfer_amount(), 50);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_owner_update_without_auth_is_rejected() {
+    let env = Env::default();
+    let (client, owner) = setup(&env);
+
+    client.update_min_transfer_amount(&owner, &50);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_collect_protocol_fees_rejects_unregistered_caller() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+
+    client.collect_protocol_fees(&Address::generate(&env), &100);
+}
+
+#[test]
+fn test_collect_and_claim_protocol_fees() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let aggregator = Address::generate(&env);
+    client.update_yield_aggregator(&owner, &Some(aggregator.clone()));
+
+    client.collect_protocol_fees(&aggregator, &100);
+    assert_eq!(env.auths()[0].0, aggregator);
+    assert_eq!(client.get_protocol_fees_collected(), 100);
+
+    client.claim_protocol_fees(&owner, &Address::generate(&env), &60);
+    assert_eq!(env.auths()[0].0, owner);
+    assert_eq!(client.get_protocol_fees_collected(), 40);
+}
+
+#[test]
+fn test_emergency_transfers_halve_tiered_approvals_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let admin = client.get_authorized_admins().get(1).unwrap();
+    client.update_approval_tiers(
+        &owner,
+        &vec![
+            &env,
+            ApprovalTier { min_amount: 0, required_approvals: 1 },
+            ApprovalTier { min_amount: 1000, required_approvals: 3 },
+        ],
+    );
+
+    let small_id = Bytes::from_slice(&env, b"small");
+    client.submit_transfer(&admin, &small_id, &transfer_params(&env, 100, false));
+    let large_id = Bytes::from_slice(&env, b"large");
+    client.submit_transfer(&admin, &large_id, &transfer_params(&env, 5000, false));
+    let emergency_id = Bytes::from_slice(&env, b"emergency");
+    client.submit_transfer(&admin, &emergency_id, &transfer_params(&env, 5000, true));
+
+    assert_eq!(client.get_pending_transfer(&small_id).required_approvals, 1);
+    assert_eq!(client.get_pending_transfer(&large_id).required_approvals, 3);
+    assert_eq!(client.get_pending_transfer(&emergency_id).required_approvals, 2);
+}
+
+fn approved_emergency_transfer(env: &Env, client: &TreasuryClient, owner: &Address) -> Bytes {
+    let admins = client.get_authorized_admins();
+    client.add_funds(owner, &10000, &Symbol::new(env, "seed"));
+
+    let transfer_id = Bytes::from_slice(env, b"emergency");
+    client.submit_transfer(&admins.get(1).unwrap(), &transfer_id, &transfer_params(env, 500, true));
+    client.approve_transfer(&admins.get(1).unwrap(), &transfer_id, &Symbol::new(env, "ok"));
+    client.approve_transfer(&admins.get(2).unwrap(), &transfer_id, &Symbol::new(env, "ok"));
+    transfer_id
+}
+
+#[test]
+fn test_emergency_transfer_is_held_by_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+
+    let transfer_id = approved_emergency_transfer(&env, &client, &owner);
+
+    assert_eq!(client.can_execute(&transfer_id), (false, Symbol::new(&env, "in_cooldown")));
+    assert_eq!(client.get_stats().executed_transfers, 0);
+}
+
+#[test]
+fn test_force_execute_with_second_admin_confirmation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let transfer_id = approved_emergency_transfer(&env, &client, &owner);
+
+    client.confirm_force_execute(&client.get_authorized_admins().get(2).unwrap(), &transfer_id);
+    client.force_execute(&owner, &transfer_id);
+
+    assert_eq!(client.get_stats().executed_transfers, 1);
+    assert!(client.get_all_pending_transfers().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_force_execute_rejects_owner_only_confirmation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let transfer_id = approved_emergency_transfer(&env, &client, &owner);
+
+    client.confirm_force_execute(&owner, &transfer_id);
+    client.force_execute(&owner, &transfer_id);
+}
+
+#[test]
+fn test_owner_transfer_under_auto_approve_limit_executes_immediately() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.add_funds(&owner, &10000, &Symbol::new(&env, "seed"));
+    client.update_owner_auto_approve_limit(&owner, &1000);
+
+    client.submit_transfer(&owner, &Bytes::from_slice(&env, b"small"), &transfer_params(&env, 500, false));
+
+    let stats = client.get_stats();
+    assert_eq!(stats.pending_transfers, 0);
+    assert_eq!(stats.executed_transfers, 1);
+    assert_eq!(stats.total_transferred, 500);
+    assert!(client.get_all_pending_transfers().is_empty());
+}
+
+#[test]
+fn test_owner_transfer_over_auto_approve_limit_stays_pending() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.add_funds(&owner, &10000, &Symbol::new(&env, "seed"));
+    client.update_owner_auto_approve_limit(&owner, &1000);
+
+    let transfer_id = Bytes::from_slice(&env, b"large");
+    client.submit_transfer(&owner, &transfer_id, &transfer_params(&env, 5000, false));
+
+    assert!(client.get_pending_transfer(&transfer_id).is_pending());
+    assert_eq!(client.get_stats().executed_transfers, 0);
+}
+
+#[test]
+fn test_reconcile_rebalances_sub_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    client.update_token(&owner, &token);
+    client.add_funds(&owner, &1000, &Symbol::new(&env, "seed"));
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &1500);
+
+    assert_eq!(client.reconcile(&owner), 500);
+
+    let stats = client.get_stats();
+    assert_eq!(stats.total_balance, 1500);
+    assert_eq!(
+        stats.insurance_fund_balance + stats.operational_fund_balance + stats.emergency_fund_balance,
+        1500,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_constructor_cannot_run_twice() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let attacker = Address::generate(&env);
+
+    env.as_contract(&client.address, || {
+        Treasury::__constructor(env.clone(), attacker.clone(), vec![&env, attacker.clone()], attacker.clone());
+    });
+}
+
+#[test]
+fn test_category_totals_track_executed_transfers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.add_funds(&owner, &10000, &Symbol::new(&env, "seed"));
+
+    let payroll = TransferParams { category: TransferCategory::Payroll, ..transfer_params(&env, 300, false) };
+    client.submit_transfer(&owner, &Bytes::from_slice(&env, b"payroll-1"), &payroll);
+    let payroll = TransferParams { category: TransferCategory::Payroll, ..transfer_params(&env, 200, false) };
+    client.submit_transfer(&owner, &Bytes::from_slice(&env, b"payroll-2"), &payroll);
+    let grant = TransferParams { category: TransferCategory::Grant, ..transfer_params(&env, 700, false) };
+    client.submit_transfer(&owner, &Bytes::from_slice(&env, b"grant-1"), &grant);
+
+    let totals = client.get_category_totals();
+    assert_eq!(totals.payroll, 500);
+    assert_eq!(totals.grant, 700);
+    assert_eq!(totals.ops, 0);
+    assert_eq!(client.get_stats().total_transferred, 1200);
+}
+
+#[test]
+fn test_repeated_auto_executed_transfers_keep_stats_consistent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.add_funds(&owner, &10000, &Symbol::new(&env, "seed"));
+
+    client.submit_transfer(&owner, &Bytes::from_slice(&env, b"first"), &transfer_params(&env, 400, false));
+    client.submit_transfer(&owner, &Bytes::from_slice(&env, b"second"), &transfer_params(&env, 600, false));
+
+    let stats = client.get_stats();
+    assert_eq!(stats.pending_transfers, 0);
+    assert_eq!(stats.executed_transfers, 2);
+    assert_eq!(stats.total_transferred, 1000);
+    assert_eq!(client.get_category_totals().ops, 1000);
+    assert!(client.get_all_pending_transfers().is_empty());
+}
+
+#[test]
+fn test_add_funds_keeps_sub_funds_consistent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+
+    for amount in [1000, 333, 2667] {
+        client.add_funds(&owner, &amount, &Symbol::new(&env, "seed"));
+    }
+
+    let stats = client.get_stats();
+    assert_eq!(stats.total_balance, 4000);
+    assert_eq!(stats.insurance_fund_balance, 2400);
+    assert_eq!(stats.operational_fund_balance, 1200);
+    assert_eq!(stats.emergency_fund_balance, 400);
+}
+
+#[test]
+fn test_expired_approval_no_longer_counts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let admins = client.get_authorized_admins();
+    let (first, second) = (admins.get(1).unwrap(), admins.get(2).unwrap());
+    client.add_funds(&owner, &10000, &Symbol::new(&env, "seed"));
+    let transfer_id = Bytes::from_slice(&env, b"transfer");
+    client.submit_transfer(&first, &transfer_id, &transfer_params(&env, 500, false));
+
+    client.approve_transfer(&first, &transfer_id, &Symbol::new(&env, "ok"));
+    let validity_period = client.get_approval_validity_period();
+    env.ledger().with_mut(|ledger| ledger.timestamp += validity_period);
+    client.approve_transfer(&second, &transfer_id, &Symbol::new(&env, "ok"));
+    client.approve_transfer(&owner, &transfer_id, &Symbol::new(&env, "ok"));
+
+    let transfer = client.get_pending_transfer(&transfer_id);
+    assert!(transfer.is_pending());
+    assert_eq!(transfer.valid_approvals(&env, validity_period), 2);
+    assert_eq!(client.can_execute(&transfer_id), (false, Symbol::new(&env, "expired")));
+
+    // Re-approving renews the expired approval and completes the threshold
+    client.approve_transfer(&first, &transfer_id, &Symbol::new(&env, "ok"));
+    assert_eq!(client.get_stats().executed_transfers, 1);
+}
+
+#[test]
+fn test_admin_details_report_roles_and_weights() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let admins = client.get_authorized_admins();
+    let operator = admins.get(1).unwrap();
+    let operator_roles = Role::Admin as u32 | Role::Operator as u32;
+    client.set_admin_roles(&owner, &operator, &operator_roles);
+    client.set_admin_weight(&owner, &operator, &3);
+    client.set_admin_weight(&owner, &admins.get(2).unwrap(), &2);
+
+    let details = client.get_admin_details();
+
+    assert_eq!(details.len(), 3);
+    let expected = [
+        (owner, Role::Admin as u32, 1),
+        (operator, operator_roles, 3),
+        (admins.get(2).unwrap(), Role::Admin as u32, 2),
+    ];
+    for (info, (address, roles, weight)) in details.iter().zip(expected) {
+        assert_eq!(info.address, address);
+        assert_eq!(info.roles, roles);
+        assert_eq!(info.weight, weight);
+    }
+}
+
+#[test]
+fn test_assets_are_tracked_independently() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let funder = Address::generate(&env);
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let xlm = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    for (asset, amount) in [(&usdc, 1000), (&xlm, 2000)] {
+        client.add_asset(&owner, asset);
+        token::StellarAssetClient::new(&env, asset).mint(&funder, &amount);
+        client.deposit_asset(&funder, asset, &amount);
+    }
+
+    let params = TransferParams { asset: Some(usdc.clone()), ..transfer_params(&env, 300, false) };
+    let recipient = params.to.clone();
+    client.submit_transfer(&owner, &Bytes::from_slice(&env, b"usdc-payout"), &params);
+
+    assert_eq!(client.get_asset_balance(&usdc), 700);
+    assert_eq!(client.get_asset_balance(&xlm), 2000);
+    assert_eq!(token::Client::new(&env, &usdc).balance(&recipient), 300);
+    assert_eq!(token::Client::new(&env, &usdc).balance(&client.address), 700);
+}
+
+#[test]
+fn test_max_transfer_increase_waits_for_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+
+    client.update_max_transfer_amount(&owner, &20000);
+    assert_eq!(client.get_max_transfer_amount(), 10000);
+    assert_eq!(client.get_pending_limit_increase().unwrap().amount, 20000);
+
+    let delay = client.get_limit_increase_delay();
+    env.ledger().with_mut(|ledger| ledger.timestamp += delay);
+    client.apply_max_transfer_increase(&owner);
+
+    assert_eq!(client.get_max_transfer_amount(), 20000);
+    assert!(client.get_pending_limit_increase().is_none());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_max_transfer_increase_before_timelock_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.update_max_transfer_amount(&owner, &20000);
+
+    client.apply_max_transfer_increase(&owner);
+}
+
+#[test]
+fn test_max_transfer_decrease_applies_immediately() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+
+    client.update_max_transfer_amount(&owner, &5000);
+
+    assert_eq!(client.get_max_transfer_amount(), 5000);
+    assert!(client.get_pending_limit_increase().is_none());
+}
+
+fn require_single_approval(env: &Env, client: &TreasuryClient, owner: &Address) {
+    client.update_approval_tiers(owner, &vec![env, ApprovalTier { min_amount: 0, required_approvals: 1 }]);
+}
+
+#[test]
+fn test_can_execute_reports_each_blocker() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    require_single_approval(&env, &client, &owner);
+    let admin = client.get_authorized_admins().get(1).unwrap();
+    let transfer_id = Bytes::from_slice(&env, b"transfer");
+    let reason = |reason: &str| (false, Symbol::new(&env, reason));
+
+    client.submit_transfer(&admin, &transfer_id, &transfer_params(&env, 500, false));
+    assert_eq!(client.can_execute(&transfer_id), reason("insufficient_approvals"));
+
+    client.approve_transfer(&admin, &transfer_id, &Symbol::new(&env, "ok"));
+    assert_eq!(client.can_execute(&transfer_id), reason("in_cooldown"));
+
+    let cooldown = client.get_emergency_cooldown();
+    env.ledger().with_mut(|ledger| ledger.timestamp += cooldown);
+    assert_eq!(client.can_execute(&transfer_id), reason("insufficient_balance"));
+
+    client.add_funds(&owner, &10000, &Symbol::new(&env, "seed"));
+    assert_eq!(client.can_execute(&transfer_id), (true, Symbol::new(&env, "executable")));
+
+    let validity_period = client.get_approval_validity_period();
+    env.ledger().with_mut(|ledger| ledger.timestamp += validity_period);
+    assert_eq!(client.can_execute(&transfer_id), reason("expired"));
+}
+
+#[test]
+fn test_can_execute_reports_emergency_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    require_single_approval(&env, &client, &owner);
+    client.update_emergency_limit(&owner, &100, &86400);
+    client.add_funds(&owner, &10000, &Symbol::new(&env, "seed"));
+    let admin = client.get_authorized_admins().get(1).unwrap();
+    let transfer_id = Bytes::from_slice(&env, b"emergency");
+    client.submit_transfer(&admin, &transfer_id, &transfer_params(&env, 500, true));
+    client.approve_transfer(&admin, &transfer_id, &Symbol::new(&env, "ok"));
+
+    let cooldown = client.get_emergency_cooldown();
+    env.ledger().with_mut(|ledger| ledger.timestamp += cooldown);
+
+    assert_eq!(client.can_execute(&transfer_id), (false, Symbol::new(&env, "emergency_limit")));
+}
+
+/// Submit a transfer and delegate the first non-owner admin's approvals for an hour
+fn delegated_transfer(env: &Env, client: &TreasuryClient, owner: &Address, delegate: &Address) -> Bytes {
+    let admin = client.get_authorized_admins().get(1).unwrap();
+    client.add_funds(owner, &10000, &Symbol::new(env, "seed"));
+    let transfer_id = Bytes::from_slice(env, b"transfer");
+    client.submit_transfer(&admin, &transfer_id, &transfer_params(env, 500, false));
+    client.delegate_approval(&admin, delegate, &(env.ledger().timestamp() + 3600));
+    transfer_id
+}
+
+#[test]
+fn test_delegate_approves_on_behalf_of_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let admin = client.get_authorized_admins().get(1).unwrap();
+    let delegate = Address::generate(&env);
+    let transfer_id = delegated_transfer(&env, &client, &owner, &delegate);
+    assert_eq!(client.get_delegation(&admin).unwrap().delegate, delegate);
+
+    client.approve_transfer(&delegate, &transfer_id, &Symbol::new(&env, "ok"));
+
+    assert_eq!(env.auths()[0].0, delegate);
+    let transfer = client.get_pending_transfer(&transfer_id);
+    let validity_period = client.get_approval_validity_period();
+    assert_eq!(transfer.valid_approvals(&env, validity_period), 1);
+    assert!(transfer.has_approved(&admin, &env, validity_period));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_delegate_and_admin_cannot_both_approve() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let admin = client.get_authorized_admins().get(1).unwrap();
+    let delegate = Address::generate(&env);
+    let transfer_id = delegated_transfer(&env, &client, &owner, &delegate);
+
+    client.approve_transfer(&delegate, &transfer_id, &Symbol::new(&env, "ok"));
+    client.approve_transfer(&admin, &transfer_id, &Symbol::new(&env, "ok"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_expired_delegate_approval_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let delegate = Address::generate(&env);
+    let transfer_id = delegated_transfer(&env, &client, &owner, &delegate);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 3600);
+
+    client.approve_transfer(&delegate, &transfer_id, &Symbol::new(&env, "ok"));
+}
+
+/// Hold 5000 of a primary token and 1000 of a supported asset, with a beneficiary pre-set
+fn treasury_with_beneficiary(env: &Env, client: &TreasuryClient, owner: &Address) -> (Address, Address, Address) {
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env)).address();
+    client.update_token(owner, &token);
+    token::StellarAssetClient::new(env, &token).mint(&client.address, &5000);
+
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(env)).address();
+    client.add_asset(owner, &asset);
+    let funder = Address::generate(env);
+    token::StellarAssetClient::new(env, &asset).mint(&funder, &1000);
+    client.deposit_asset(&funder, &asset, &1000);
+
+    let beneficiary = Address::generate(env);
+    client.update_emergency_beneficiary(owner, &beneficiary);
+    (token, asset, beneficiary)
+}
+
+#[test]
+fn test_emergency_sweep_moves_everything_to_beneficiary() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let (token, asset, beneficiary) = treasury_with_beneficiary(&env, &client, &owner);
+    client.emergency_shutdown(&owner, &Symbol::new(&env, "breach"));
+
+    assert_eq!(client.emergency_sweep(&owner, &beneficiary), 5000);
+
+    assert_eq!(token::Client::new(&env, &token).balance(&beneficiary), 5000);
+    assert_eq!(token::Client::new(&env, &asset).balance(&beneficiary), 1000);
+    assert_eq!(token::Client::new(&env, &token).balance(&client.address), 0);
+    assert_eq!(client.get_asset_balance(&asset), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_emergency_sweep_outside_shutdown_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let (_, _, beneficiary) = treasury_with_beneficiary(&env, &client, &owner);
+
+    client.emergency_sweep(&owner, &beneficiary);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_emergency_sweep_to_other_address_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    treasury_with_beneficiary(&env, &client, &owner);
+    client.emergency_shutdown(&owner, &Symbol::new(&env, "breach"));
+
+    client.emergency_sweep(&owner, &Address::generate(&env));
+}
+
+/// Submit and approve an emergency transfer, then wait out its cooldown
+fn ready_emergency_transfer(env: &Env, client: &TreasuryClient, transfer_id: &[u8], amount: i128) -> Bytes {
+    let admin = client.get_authorized_admins().get(1).unwrap();
+    let transfer_id = Bytes::from_slice(env, transfer_id);
+    client.submit_transfer(&admin, &transfer_id, &transfer_params(env, amount, true));
+    client.approve_transfer(&admin, &transfer_id, &Symbol::new(env, "ok"));
+    let cooldown = client.get_emergency_cooldown();
+    env.ledger().with_mut(|ledger| ledger.timestamp += cooldown);
+    transfer_id
+}
+
+/// Execute emergency transfers totalling the 1000 cap of a one-day window
+fn exhaust_emergency_limit(env: &Env, client: &TreasuryClient, owner: &Address) {
+    require_single_approval(env, client, owner);
+    client.update_emergency_limit(owner, &1000, &86400);
+    client.add_funds(owner, &10000, &Symbol::new(env, "seed"));
+    for (transfer_id, amount) in [(b"emergency-1", 600), (b"emergency-2", 400)] {
+        let transfer_id = ready_emergency_transfer(env, client, transfer_id, amount);
+        client.execute_transfer(owner, &transfer_id);
+    }
+    assert_eq!(client.get_emergency_spent(), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_emergency_transfer_over_window_limit_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    exhaust_emergency_limit(&env, &client, &owner);
+
+    let transfer_id = ready_emergency_transfer(&env, &client, b"emergency-3", 100);
+    client.execute_transfer(&owner, &transfer_id);
+}
+
+#[test]
+fn test_emergency_limit_resets_with_the_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    exhaust_emergency_limit(&env, &client, &owner);
+
+    let transfer_id = ready_emergency_transfer(&env, &client, b"emergency-3", 100);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86400);
+    client.execute_transfer(&owner, &transfer_id);
+
+    assert_eq!(client.get_emergency_spent(), 100);
+    assert_eq!(client.get_stats().executed_transfers, 3);
+}
+
+/// Submit a transfer, approve it once and dispute it
+fn disputed_transfer(env: &Env, client: &TreasuryClient, owner: &Address) -> Bytes {
+    let admins = client.get_authorized_admins();
+    client.add_funds(owner, &10000, &Symbol::new(env, "seed"));
+    let transfer_id = Bytes::from_slice(env, b"transfer");
+    client.submit_transfer(&admins.get(1).unwrap(), &transfer_id, &transfer_params(env, 500, false));
+    client.approve_transfer(&admins.get(1).unwrap(), &transfer_id, &Symbol::new(env, "ok"));
+    client.dispute_transfer(&admins.get(2).unwrap(), &transfer_id, &Symbol::new(env, "contested"));
+    transfer_id
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_disputed_transfer_cannot_be_approved() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let transfer_id = disputed_transfer(&env, &client, &owner);
+    assert_eq!(client.get_pending_transfer(&transfer_id).status, TransferStatus::Disputed);
+
+    client.approve_transfer(&owner, &transfer_id, &Symbol::new(&env, "ok"));
+}
+
+#[test]
+fn test_overruled_dispute_returns_transfer_to_pending() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let transfer_id = disputed_transfer(&env, &client, &owner);
+
+    client.resolve_dispute(&owner, &transfer_id, &false);
+
+    let transfer = client.get_pending_transfer(&transfer_id);
+    assert_eq!(transfer.status, TransferStatus::Pending);
+    assert_eq!(transfer.approvals, 1);
+    client.approve_transfer(&owner, &transfer_id, &Symbol::new(&env, "ok"));
+    assert_eq!(client.get_pending_transfer(&transfer_id).approvals, 2);
+}
+
+#[test]
+fn test_upheld_dispute_rejects_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let transfer_id = disputed_transfer(&env, &client, &owner);
+    assert_eq!(client.get_stats().pending_transfers, 1);
+
+    client.resolve_dispute(&owner, &transfer_id, &true);
+
+    assert_eq!(client.get_stats().pending_transfers, 0);
+    assert!(client.get_all_pending_transfers().is_empty());
+    assert_eq!(client.get_stats().executed_transfers, 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_transfer_below_minimum_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.update_min_transfer_amount(&owner, &100);
+    let admin = client.get_authorized_admins().get(1).unwrap();
+
+    client.submit_transfer(&admin, &Bytes::from_slice(&env, b"dust"), &transfer_params(&env, 99, false));
+}
+
+#[test]
+fn test_transfer_at_minimum_is_accepted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.update_min_transfer_amount(&owner, &100);
+    let admin = client.get_authorized_admins().get(1).unwrap();
+    let transfer_id = Bytes::from_slice(&env, b"transfer");
+
+    client.submit_transfer(&admin, &transfer_id, &transfer_params(&env, 100, false));
+
+    assert_eq!(client.get_pending_transfer(&transfer_id).amount, 100);
+}
+
+#[test]
+fn test_cosigned_fund_allocation_applies_only_after_cosign() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.set_owner_cosign_required(&owner, &true);
+
+    let allocation = FundAllocation { insurance_percentage: 50, operational_percentage: 30, emergency_percentage: 20 };
+    client.update_fund_allocation(&owner, &allocation);
+    assert_eq!(client.get_fund_allocation().insurance_percentage, 60);
+    assert!(client.get_pending_owner_action(&0).is_some());
+
+    client.cosign_owner_action(&client.get_authorized_admins().get(1).unwrap(), &0);
+
+    let applied = client.get_fund_allocation();
+    assert_eq!((applied.insurance_percentage, applied.emergency_percentage), (50, 20));
+    assert!(client.get_pending_owner_action(&0).is_none());
+}
+
+#[test]
+fn test_cosigned_emergency_shutdown_applies_only_after_cosign() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.set_owner_cosign_required(&owner, &true);
+
+    client.emergency_shutdown(&owner, &Symbol::new(&env, "breach"));
+    assert!(!client.is_emergency_shutdown());
+
+    client.cosign_owner_action(&client.get_authorized_admins().get(2).unwrap(), &0);
+    assert!(client.is_emergency_shutdown());
+}
+
+#[test]
+fn test_cosigned_config_updates_apply_only_after_cosign() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.set_owner_cosign_required(&owner, &true);
+    let (token, drawer) = (Address::generate(&env), Address::generate(&env));
+
+    client.update_token(&owner, &token);
+    client.update_emergency_drawer(&owner, &Some(drawer.clone()));
+    client.update_force_confirmation_window(&owner, &7200);
+    client.update_owner_auto_approve_limit(&owner, &0);
+    client.update_emergency_cooldown(&owner, &60);
+    assert_eq!(client.get_token(), None);
+    assert_eq!(client.get_emergency_drawer(), None);
+    assert_eq!(client.get_force_confirmation_window(), 3600);
+    assert_eq!(client.get_owner_auto_approve_limit(), 10000);
+    assert_eq!(client.get_emergency_cooldown(), 3600);
+
+    let cosigner = client.get_authorized_admins().get(1).unwrap();
+    for action_id in 0..5 {
+        client.cosign_owner_action(&cosigner, &action_id);
+    }
+
+    assert_eq!(client.get_token(), Some(token));
+    assert_eq!(client.get_emergency_drawer(), Some(drawer));
+    assert_eq!(client.get_force_confirmation_window(), 7200);
+    assert_eq!(client.get_owner_auto_approve_limit(), 0);
+    assert_eq!(client.get_emergency_cooldown(), 60);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_owner_cannot_cosign_own_action() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.set_owner_cosign_required(&owner, &true);
+    client.emergency_shutdown(&owner, &Symbol::new(&env, "breach"));
+
+    client.cosign_owner_action(&owner, &0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_cosign_after_window_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    client.set_owner_cosign_required(&owner, &true);
+    client.emergency_shutdown(&owner, &Symbol::new(&env, "breach"));
+    let window = client.get_owner_cosign_window();
+    env.ledger().with_mut(|ledger| ledger.timestamp += window + 1);
+
+    client.cosign_owner_action(&client.get_authorized_admins().get(1).unwrap(), &0);
+}
+
+#[test]
+fn test_first_payout_to_recipient_needs_extra_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    require_single_approval(&env, &client, &owner);
+    client.update_new_recipient_approvals(&owner, &1);
+    client.add_funds(&owner, &10000, &Symbol::new(&env, "seed"));
+    let admins = client.get_authorized_admins();
+    let (first, second) = (admins.get(1).unwrap(), admins.get(2).unwrap());
+    let params = transfer_params(&env, 500, false);
+    let recipient = params.to.clone();
+
+    let first_payout = Bytes::from_slice(&env, b"first-payout");
+    client.submit_transfer(&first, &first_payout, &params);
+    assert_eq!(client.get_pending_transfer(&first_payout).required_approvals, 2);
+    assert!(!client.is_known_recipient(&recipient));
+    client.approve_transfer(&first, &first_payout, &Symbol::new(&env, "ok"));
+    client.approve_transfer(&second, &first_payout, &Symbol::new(&env, "ok"));
+    let cooldown = client.get_emergency_cooldown();
+    env.ledger().with_mut(|ledger| ledger.timestamp += cooldown);
+    client.execute_transfer(&first, &first_payout);
+    assert!(client.is_known_recipient(&recipient));
+
+    let repeat_payout = Bytes::from_slice(&env, b"repeat-payout");
+    client.submit_transfer(&first, &repeat_payout, &params);
+    assert_eq!(client.get_pending_transfer(&repeat_payout).required_approvals, 1);
+}
+
+/// Enable a one-week heartbeat with the first non-owner admin as recovery admin
+fn enable_heartbeat(client: &TreasuryClient, owner: &Address) -> Address {
+    let recovery_admin = client.get_authorized_admins().get(1).unwrap();
+    client.update_heartbeat(owner, &(7 * 86400), &recovery_admin);
+    recovery_admin
+}
+
+#[test]
+fn test_missed_heartbeat_enables_owner_recovery() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let recovery_admin = enable_heartbeat(&client, &owner);
+    let new_owner = Address::generate(&env);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 7 * 86400 + 1);
+    assert!(client.is_heartbeat_missed());
+
+    client.initiate_owner_recovery(&recovery_admin, &new_owner);
+    assert_eq!(client.get_pending_owner_recovery(), Some(new_owner.clone()));
+    client.accept_owner_recovery(&new_owner);
+
+    assert_eq!(env.auths()[0].0, new_owner);
+    assert_eq!(client.get_owner(), new_owner);
+    assert!(!client.is_heartbeat_missed());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_timely_heartbeat_keeps_recovery_disabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let recovery_admin = enable_heartbeat(&client, &owner);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 7 * 86400);
+    client.heartbeat(&owner);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 7 * 86400);
+    assert!(!client.is_heartbeat_missed());
+
+    client.initiate_owner_recovery(&recovery_admin, &Address::generate(&env));
+}
+
+#[test]
+fn test_owner_heartbeat_cancels_recovery_in_progress() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env);
+    let recovery_admin = enable_heartbeat(&client, &owner);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 7 * 86400 + 1);
+    client.initiate_owner_recovery(&recovery_admin, &Address::generate(&env));
+
+    client.heartbeat(&owner);
+
+    assert_eq!(client.get_pending_owner_recovery(), None);
+    assert_eq!(client.get_owner(), owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_get_owner_before_construction_returns_not_initialized() {
+    let env = Env::default();
+    let client = TreasuryClient::new(&env, &env.register_contract(None, Treasury));
+
+    client.get_owner();
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_get_missing_pending_transfer_returns_not_found() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+
+    client.get_pending_transfer(&Bytes::from_slice(&env, b"missing"));
+}
@@ -0,0 +1,39 @@
+//! Integration tests for the simple YieldAggregator contract
+
+use contracts::yield_aggregator_simple::{YieldAggregator, YieldAggregatorClient};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, Bytes, Env};
+
+fn setup(env: &Env) -> YieldAggregatorClient<'_> {
+    let contract_id = env.register_contract(None, YieldAggregator);
+    YieldAggregatorClient::new(env, &contract_id)
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_deposit_rejects_non_positive_amount() {
+    let env = Env::default();
+    let client = setup(&env);
+    let depositor = Address::generate(&env);
+
+    client.deposit(&depositor, &0, &10);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_deposit_rejects_insurance_percentage_above_100() {
+    let env = Env::default();
+    let client = setup(&env);
+    let depositor = Address::generate(&env);
+
+    client.deposit(&depositor, &1000, &101);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_get_missing_deposit_returns_not_found() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    client.get_deposit(&Bytes::from_array(&env, &[0xff; 32]));
+}
@@ -0,0 +1,735 @@
+//! Integration tests for the full YieldAggregator contract
+
+mod common;
+
+use contracts::yield_aggregator::{
+    DepositParams, DepositStatus, WithdrawParams, YieldAggregator, YieldAggregatorClient, YieldAllocation,
+    MAX_EARLY_WITHDRAWAL_PENALTY_BPS, MAX_FEE_BPS, YIELD_PRECISION,
+};
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::{vec, Address, Bytes, Env, Symbol, TryFromVal, Val};
+
+fn setup(env: &Env) -> YieldAggregatorClient<'_> {
+    common::deploy_yield_aggregator(
+        env,
+        &Address::generate(env),
+        &Address::generate(env),
+        &Address::generate(env),
+        &Address::generate(env),
+    )
+}
+
+fn deposit_params(env: &Env, depositor: &Address, amount: i128) -> DepositParams {
+    DepositParams {
+        depositor: depositor.clone(),
+        amount,
+        pool_id: Bytes::from_slice(env, b"pool"),
+        allocate_to_insurance: true,
+        custom_insurance_percentage: None,
+        lock_duration: 0,
+        referrer: None,
+    }
+}
+
+fn deposit(env: &Env, client: &YieldAggregatorClient, depositor: &Address, amount: i128) -> Bytes {
+    let deposit_id = Bytes::from_slice(env, b"deposit-1");
+    client.deposit(&deposit_id, &deposit_params(env, depositor, amount));
+    deposit_id
+}
+
+fn advance_time(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|ledger| ledger.timestamp += seconds);
+}
+
+fn withdraw_params(depositor: &Address, amount: i128) -> WithdrawParams {
+    WithdrawParams {
+        depositor: depositor.clone(),
+        amount,
+        from_insurance: true,
+        from_yield: true,
+    }
+}
+
+#[test]
+fn test_partial_withdrawal_leaving_minimum_succeeds() {
+    let env = Env::default();
+    let client = setup(&env);
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1000);
+
+    // Leaves 101, just above the 100 minimum deposit
+    let receipt = client.withdraw(&deposit_id, &withdraw_params(&depositor, 899));
+
+    assert_eq!(receipt.net_amount, 899);
+    assert_eq!(client.get_deposit(&deposit_id).total_value(), 101);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_partial_withdrawal_leaving_dust_is_rejected() {
+    let env = Env::default();
+    let client = setup(&env);
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1000);
+
+    client.withdraw(&deposit_id, &withdraw_params(&depositor, 950));
+}
+
+#[test]
+fn test_full_withdrawal_succeeds() {
+    let env = Env::default();
+    let client = setup(&env);
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1000);
+
+    let receipt = client.withdraw(&deposit_id, &withdraw_params(&depositor, 1000));
+
+    assert_eq!(receipt.net_amount, 1000);
+    assert_eq!(client.get_deposit(&deposit_id).total_value(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_operator_update_requires_operator_auth() {
+    let env = Env::default();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+
+    client.update_min_deposit(&operator, &500);
+}
+
+#[test]
+fn test_tvl_breakdown_sums_deposits_with_different_splits() {
+    let env = Env::default();
+    let client = setup(&env);
+    client.deposit(
+        &Bytes::from_slice(&env, b"deposit-1"),
+        &DepositParams { custom_insurance_percentage: Some(10), ..deposit_params(&env, &Address::generate(&env), 1000) },
+    );
+    client.deposit(
+        &Bytes::from_slice(&env, b"deposit-2"),
+        &DepositParams { custom_insurance_percentage: Some(50), ..deposit_params(&env, &Address::generate(&env), 3000) },
+    );
+
+    let breakdown = client.get_tvl_breakdown();
+
+    assert_eq!(breakdown.total_insurance_allocation, 100 + 1500);
+    assert_eq!(breakdown.total_yield_allocation, 900 + 1500);
+    assert_eq!(breakdown.total_yield_earned, 0);
+    assert_eq!(breakdown.total_insurance_allocation + breakdown.total_yield_allocation, 4000);
+}
+
+#[test]
+fn test_cooldown_blocks_user_but_not_exempt_operator() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1_000_000);
+    advance_time(&env, 3600);
+
+    // Well inside the 24h cooldown, the depositor's own claim is skipped
+    client.claim_yield(&depositor, &None);
+    assert_eq!(client.get_deposit(&deposit_id).yield_earned, 0);
+
+    client.add_cooldown_exemption(&operator, &operator);
+    assert!(client.is_cooldown_exempt(&operator));
+    assert!(!client.is_cooldown_exempt(&depositor));
+
+    client.harvest_all(&operator, &vec![&env, depositor.clone()]);
+    assert!(client.get_deposit(&deposit_id).yield_earned > 0);
+}
+
+#[test]
+fn test_force_close_refunds_long_inactive_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1000);
+    advance_time(&env, client.get_inactivity_threshold() + 86400);
+
+    client.force_close_deposit(&operator, &deposit_id);
+
+    assert!(client.get_user_deposits(&depositor).is_empty());
+    assert_eq!(client.get_total_balance(), 0);
+    assert_eq!(client.get_stats().active_deposits, 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_force_close_rejects_recently_active_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1000);
+    advance_time(&env, client.get_inactivity_threshold() - 1);
+
+    client.force_close_deposit(&operator, &deposit_id);
+}
+
+#[test]
+fn test_project_yield_matches_apy_and_accrual() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    // 5% APY on the 8000 yield portion over a year
+    assert_eq!(client.project_yield(&10000, &20, &365), 400);
+
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 10000);
+    advance_time(&env, 30 * 86400);
+    client.claim_yield(&depositor, &None);
+
+    assert_eq!(client.get_deposit(&deposit_id).yield_earned, client.project_yield(&10000, &20, &30));
+}
+
+#[test]
+fn test_full_withdrawal_emits_status_transitions() {
+    let env = Env::default();
+    let client = setup(&env);
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1000);
+
+    client.withdraw(&deposit_id, &withdraw_params(&depositor, 1000));
+
+    let transitions: std::vec::Vec<(DepositStatus, DepositStatus)> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()) == Ok(Symbol::new(&env, "deposit_status_changed"))
+        })
+        .map(|(_, _, data)| <_>::try_from_val(&env, &data).unwrap())
+        .collect();
+    assert_eq!(
+        transitions,
+        [
+            (DepositStatus::Active, DepositStatus::Withdrawing),
+            (DepositStatus::Withdrawing, DepositStatus::Withdrawn),
+        ],
+    );
+    assert_eq!(client.get_deposit_status(&deposit_id), DepositStatus::Withdrawn);
+}
+
+#[test]
+fn test_rebalance_deposit_moves_difference_to_insurance_fund() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let depositor = Address::generate(&env);
+    let deposit_id = Bytes::from_slice(&env, b"deposit-1");
+    client.deposit(
+        &deposit_id,
+        &DepositParams { custom_insurance_percentage: Some(10), ..deposit_params(&env, &depositor, 1000) },
+    );
+
+    client.rebalance_deposit(&deposit_id, &YieldAllocation { insurance_percentage: 50, yield_percentage: 50 });
+
+    let deposit = client.get_deposit(&deposit_id);
+    assert_eq!(deposit.insurance_allocation, 500);
+    assert_eq!(deposit.yield_allocation, 500);
+    let stats = client.get_stats();
+    assert_eq!(stats.total_insurance_allocation, 500);
+    assert_eq!(stats.total_yield_allocation, 500);
+
+    let moved: std::vec::Vec<i128> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()) == Ok(Symbol::new(&env, "insurance_fund_transfer"))
+        })
+        .map(|(_, topics, _)| i128::try_from_val(&env, &topics.get(1).unwrap()).unwrap())
+        .collect();
+    // 100 went to the fund on deposit, the rebalance moves the other 400
+    assert_eq!(moved, [100, 400]);
+}
+
+#[test]
+fn test_reward_token_yield_is_claimed_apart_from_principal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    client.update_reward_token(&operator, &Some(Address::generate(&env)));
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1_000_000);
+    advance_time(&env, 30 * 86400);
+
+    client.claim_yield(&depositor, &None);
+    let deposit = client.get_deposit(&deposit_id);
+    let accrued = deposit.reward_earned;
+    assert!(accrued > 0);
+    assert_eq!(deposit.yield_earned, 0);
+    assert_eq!(deposit.total_value(), 1_000_000);
+
+    // Withdrawing the whole principal leaves the rewards claimable
+    client.withdraw(&deposit_id, &withdraw_params(&depositor, 1_000_000));
+    assert_eq!(client.claim_rewards(&deposit_id), accrued);
+    assert_eq!(client.get_deposit(&deposit_id).reward_earned, 0);
+}
+
+#[test]
+fn test_matured_locked_deposit_pays_reduced_withdraw_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    client.update_withdraw_fee_bps(&operator, &100);
+    client.update_matured_withdraw_fee_bps(&operator, &20);
+    let depositor = Address::generate(&env);
+    let locked_id = Bytes::from_slice(&env, b"locked");
+    client.deposit(&locked_id, &DepositParams { lock_duration: 86400, ..deposit_params(&env, &depositor, 1000) });
+    let liquid_id = Bytes::from_slice(&env, b"liquid");
+    client.deposit(&liquid_id, &deposit_params(&env, &depositor, 1000));
+    advance_time(&env, 86400);
+
+    let matured = client.withdraw(&locked_id, &withdraw_params(&depositor, 1000));
+    let liquid = client.withdraw(&liquid_id, &withdraw_params(&depositor, 1000));
+
+    assert_eq!(matured.fee, 2);
+    assert_eq!(liquid.fee, 10);
+}
+
+#[test]
+fn test_top_up_applies_stored_split() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1000);
+    let deposited_at = env.ledger().timestamp();
+    advance_time(&env, 1500);
+
+    client.top_up(&deposit_id, &500);
+
+    let deposit = client.get_deposit(&deposit_id);
+    assert_eq!(deposit.amount, 1500);
+    assert_eq!(deposit.insurance_allocation, 300);
+    assert_eq!(deposit.yield_allocation, 1200);
+    // Deposit time moves to the amount-weighted average
+    assert_eq!(deposit.deposit_time, deposited_at + 500);
+    assert_eq!(client.get_stats().total_deposits, 1500);
+    assert_eq!(client.get_total_balance(), 1500);
+}
+
+#[test]
+fn test_apy_history_keeps_latest_snapshots_in_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    client.update_apy_history_capacity(&operator, &2);
+    let depositor = Address::generate(&env);
+    deposit(&env, &client, &depositor, 1_000_000);
+
+    let mut claimed_at = std::vec::Vec::new();
+    for _ in 0..3 {
+        advance_time(&env, 30 * 86400);
+        client.claim_yield(&depositor, &None);
+        claimed_at.push(env.ledger().timestamp());
+    }
+
+    // The first snapshot was evicted; the rest are oldest first
+    let history = client.get_apy_history(&0, &10);
+    assert_eq!(history.len(), 2);
+    let (older, newer) = (history.get(0).unwrap(), history.get(1).unwrap());
+    assert_eq!((older.timestamp, newer.timestamp), (claimed_at[1], claimed_at[2]));
+    assert!(newer.apy > older.apy);
+    assert_eq!(newer.apy, client.get_stats().current_apy);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_immediate_second_harvest_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    let depositors = vec![&env, Address::generate(&env)];
+
+    client.harvest_all(&operator, &depositors);
+    client.harvest_all(&operator, &depositors);
+}
+
+#[test]
+fn test_harvest_allowed_after_configured_interval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    client.update_harvest_interval(&operator, &operator, &86400);
+    assert_eq!(client.get_harvest_interval(&operator), 86400);
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1_000_000);
+
+    advance_time(&env, 86400);
+    client.harvest_all(&operator, &vec![&env, depositor.clone()]);
+    let first_harvest = client.get_deposit(&deposit_id).yield_earned;
+    advance_time(&env, 86400);
+    client.harvest_all(&operator, &vec![&env, depositor]);
+
+    assert!(first_harvest > 0);
+    assert!(client.get_deposit(&deposit_id).yield_earned > first_harvest);
+}
+
+#[test]
+fn test_withdrawal_receipt_splits_yield_and_principal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    client.update_withdraw_fee_bps(&operator, &100);
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1_000_000);
+    advance_time(&env, 30 * 86400);
+    client.claim_yield(&depositor, &None);
+    let yield_earned = client.get_deposit(&deposit_id).yield_earned;
+    assert!(yield_earned > 0);
+
+    let amount = yield_earned + 10_000;
+    let receipt = client.withdraw(
+        &deposit_id,
+        &WithdrawParams { from_insurance: false, ..withdraw_params(&depositor, amount) },
+    );
+
+    assert_eq!(receipt.from_insurance, 0);
+    assert_eq!(receipt.from_yield_earned, yield_earned);
+    assert_eq!(receipt.from_yield_principal, 10_000);
+    assert_eq!(receipt.fee, amount / 100);
+    assert_eq!(
+        receipt.from_insurance + receipt.from_yield_principal + receipt.from_yield_earned - receipt.fee,
+        receipt.net_amount,
+    );
+    assert_eq!(receipt.remaining_value, client.get_deposit(&deposit_id).total_value());
+}
+
+#[test]
+fn test_withdrawal_receipt_splits_insurance_and_yield_allocations() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1000);
+
+    let receipt = client.withdraw(&deposit_id, &withdraw_params(&depositor, 500));
+
+    assert_eq!((receipt.from_insurance, receipt.from_yield_principal), (100, 400));
+    assert_eq!(receipt.from_yield_earned, 0);
+    assert_eq!(receipt.net_amount, 500);
+    assert_eq!(receipt.remaining_value, 500);
+}
+
+#[test]
+fn test_recommend_pool_picks_highest_apy_with_capacity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    let (low, high, full, disabled) = (
+        Bytes::from_slice(&env, b"pool-a"),
+        Bytes::from_slice(&env, b"pool-b"),
+        Bytes::from_slice(&env, b"pool-c"),
+        Bytes::from_slice(&env, b"pool-d"),
+    );
+    for pool_id in [&low, &high, &full, &disabled] {
+        client.register_pool(&operator, pool_id, &10_000);
+    }
+    client.update_pool_metrics(&operator, &low, &300, &0);
+    client.update_pool_metrics(&operator, &high, &500, &0);
+    client.update_pool_metrics(&operator, &full, &900, &10_000);
+    client.update_pool_metrics(&operator, &disabled, &800, &0);
+    client.set_pool_enabled(&operator, &disabled, &false);
+
+    assert_eq!(client.recommend_pool(), high);
+}
+
+#[test]
+fn test_recommend_pool_breaks_ties_by_lowest_id() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    let (first, second) = (Bytes::from_slice(&env, b"pool-a"), Bytes::from_slice(&env, b"pool-b"));
+    client.register_pool(&operator, &second, &10_000);
+    client.register_pool(&operator, &first, &10_000);
+    client.update_pool_metrics(&operator, &second, &500, &0);
+    client.update_pool_metrics(&operator, &first, &500, &0);
+
+    assert_eq!(client.recommend_pool(), first);
+}
+
+#[test]
+fn test_fee_setters_accept_values_at_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+
+    client.update_deposit_fee_bps(&operator, &MAX_FEE_BPS);
+    client.update_withdraw_fee_bps(&operator, &MAX_FEE_BPS);
+    client.update_matured_withdraw_fee_bps(&operator, &MAX_FEE_BPS);
+    client.update_early_withdrawal_penalty(&operator, &MAX_EARLY_WITHDRAWAL_PENALTY_BPS);
+
+    assert_eq!(client.get_deposit_fee_bps(), MAX_FEE_BPS);
+    assert_eq!(client.get_withdraw_fee_bps(), MAX_FEE_BPS);
+    assert_eq!(client.get_matured_withdraw_fee_bps(), MAX_FEE_BPS);
+    assert_eq!(client.get_early_withdrawal_penalty_bps(), MAX_EARLY_WITHDRAWAL_PENALTY_BPS);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_deposit_fee_above_cap_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+
+    client.update_deposit_fee_bps(&operator, &(MAX_FEE_BPS + 1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_withdraw_fee_above_cap_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+
+    client.update_withdraw_fee_bps(&operator, &(MAX_FEE_BPS + 1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_matured_withdraw_fee_above_cap_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+
+    client.update_matured_withdraw_fee_bps(&operator, &(MAX_FEE_BPS + 1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_early_withdrawal_penalty_above_cap_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+
+    client.update_early_withdrawal_penalty(&operator, &(MAX_EARLY_WITHDRAWAL_PENALTY_BPS + 1));
+}
+
+#[test]
+fn test_referrer_accrues_and_claims_reward() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    client.fund_incentive_pool(&operator, &1000);
+    let (depositor, referrer) = (Address::generate(&env), Address::generate(&env));
+
+    client.deposit(
+        &Bytes::from_slice(&env, b"deposit-1"),
+        &DepositParams { referrer: Some(referrer.clone()), ..deposit_params(&env, &depositor, 10_000) },
+    );
+    assert_eq!(client.get_referral_rewards(&referrer), 50);
+    assert_eq!(client.get_incentive_pool_balance(), 950);
+
+    assert_eq!(client.claim_referral_rewards(&referrer), 50);
+    assert_eq!(env.auths()[0].0, referrer);
+    assert_eq!(client.get_referral_rewards(&referrer), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_self_referral_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let depositor = Address::generate(&env);
+
+    client.deposit(
+        &Bytes::from_slice(&env, b"deposit-1"),
+        &DepositParams { referrer: Some(depositor.clone()), ..deposit_params(&env, &depositor, 10_000) },
+    );
+}
+
+#[test]
+fn test_yield_drift_past_threshold_flags_deposit_until_rebalanced() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1_000_000);
+
+    // A month of yield shifts the insurance share by a few basis points, well under the default
+    advance_time(&env, 30 * 86400);
+    client.claim_yield(&depositor, &None);
+    assert!(!client.needs_rebalance(&deposit_id));
+
+    client.update_rebalance_threshold_bps(&operator, &5);
+    advance_time(&env, 30 * 86400);
+    client.claim_yield(&depositor, &None);
+    assert!(client.needs_rebalance(&deposit_id));
+
+    client.rebalance_deposit(&deposit_id, &YieldAllocation { insurance_percentage: 20, yield_percentage: 80 });
+    assert!(!client.needs_rebalance(&deposit_id));
+}
+
+/// Get the acting address and data of the latest event published under `name`
+fn published<T: TryFromVal<Env, Val>>(env: &Env, name: &str) -> (Address, T) {
+    let (_, topics, data) = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| Symbol::try_from_val(env, &topics.get(0).unwrap()) == Ok(Symbol::new(env, name)))
+        .last()
+        .unwrap();
+    let actor = Address::try_from_val(env, &topics.get(1).unwrap()).unwrap();
+    (actor, T::try_from_val(env, &data).unwrap())
+}
+
+#[test]
+fn test_config_updates_publish_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    let new_operator = Address::generate(&env);
+    let previous_cooldown = client.get_yield_claim_cooldown();
+
+    client.update_default_allocation(&operator, &YieldAllocation { insurance_percentage: 30, yield_percentage: 70 });
+    assert_eq!(published(&env, "allocation_updated"), (operator.clone(), (30u32, 70u32)));
+
+    client.update_yield_claim_cooldown(&operator, &3600);
+    assert_eq!(published(&env, "cooldown_updated"), (operator.clone(), (previous_cooldown, 3600u64)));
+
+    client.add_authorized_operator(&operator, &new_operator);
+    assert_eq!(published(&env, "operator_added"), (operator.clone(), new_operator.clone()));
+
+    client.remove_authorized_operator(&operator, &new_operator);
+    assert_eq!(published(&env, "operator_removed"), (operator, new_operator));
+}
+
+#[test]
+fn test_daily_claims_on_small_deposit_keep_fractional_yield() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1000);
+
+    // Each day accrues about 0.11 on the 800 yield allocation, which whole-unit math would drop
+    for _ in 0..60 {
+        advance_time(&env, 86400);
+        client.claim_yield(&depositor, &None);
+    }
+
+    let deposit = client.get_deposit(&deposit_id);
+    let accrued = deposit.yield_earned * YIELD_PRECISION + deposit.yield_remainder;
+    let continuous = 800 * 5 * (60 * 86400) * YIELD_PRECISION / (100 * 31_536_000);
+    assert_eq!(deposit.yield_earned, 6);
+    assert!((0..60).contains(&(continuous - accrued)));
+}
+
+#[test]
+fn test_rotate_operator_swaps_in_place() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    let (old, new) = (Address::generate(&env), Address::generate(&env));
+    client.add_authorized_operator(&operator, &old);
+
+    client.rotate_operator(&operator, &old, &new);
+
+    assert_eq!(client.get_authorized_operators(), vec![&env, operator.clone(), new.clone()]);
+    assert_eq!(published(&env, "operator_rotated"), (operator, (old, new)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_rotate_missing_operator_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+
+    client.rotate_operator(&operator, &Address::generate(&env), &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_rotate_to_existing_operator_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    let old = Address::generate(&env);
+    client.add_authorized_operator(&operator, &old);
+
+    client.rotate_operator(&operator, &old, &operator);
+}
+
+#[test]
+fn test_yield_below_minimum_is_carried_until_claimable() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let operator = client.get_authorized_operators().get(0).unwrap();
+    client.update_min_claimable_yield(&operator, &200);
+    let depositor = Address::generate(&env);
+    let deposit_id = deposit(&env, &client, &depositor, 1_000_000);
+    let deposited_at = env.ledger().timestamp();
+
+    // A day on the 800000 yield allocation accrues about 109
+    advance_time(&env, 86400);
+    client.claim_yield(&depositor, &None);
+    let deposit = client.get_deposit(&deposit_id);
+    assert_eq!(deposit.yield_earned, 0);
+    assert_eq!(deposit.last_yield_claim, deposited_at);
+
+    advance_time(&env, 86400);
+    client.claim_yield(&depositor, &None);
+    let deposit = client.get_deposit(&deposit_id);
+    assert_eq!(deposit.yield_earned, 219);
+    assert_eq!(deposit.last_yield_claim, env.ledger().timestamp());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_constructor_cannot_run_twice() {
+    let env = Env::default();
+    let client = setup(&env);
+    let attacker = Address::generate(&env);
+
+    env.as_contract(&client.address, || {
+        YieldAggregator::__constructor(
+            env.clone(),
+            attacker.clone(),
+            attacker.clone(),
+            attacker.clone(),
+            attacker.clone(),
+            20,
+            attacker.clone(),
+        );
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_get_missing_deposit_returns_not_found() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    client.get_deposit(&Bytes::from_slice(&env, b"missing"));
+}
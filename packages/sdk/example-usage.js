@@ -83,7 +83,7 @@ class ContractExample {
         .addOperation(Operation.invokeContractFunction({
           contract: this.contract,
           function: 'get_policy',
-          args: [nativeToScVal(policyId, { type: 'u32' })]
+          args: [nativeToScVal(policyId, { type: 'bytes' })]
         }))
         .setTimeout(30)
         .build();
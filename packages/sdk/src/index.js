@@ -54,7 +54,7 @@ class SmartContractSDK {
    * @param {string} holderAddress - The policy holder's Stellar address
    * @param {number} amount - The coverage amount
    * @param {string} sourceAccount - Account to pay for transaction
-   * @returns {Promise<number>} Policy ID
+   * @returns {Promise<Buffer>} Policy ID
    */
   async createPolicy(holderAddress, amount, sourceAccount = TEST_USER_ADDRESS) {
     console.log('\n📝 Creating insurance policy...');
@@ -91,7 +91,7 @@ class SmartContractSDK {
 
       if (simResult.result) {
         const policyId = scValToNative(simResult.result);
-        console.log(`✅ Policy ID created: ${policyId}`);
+        console.log(`✅ Policy ID created: ${policyId.toString('hex')}`);
         return policyId;
       }
 
@@ -105,13 +105,13 @@ class SmartContractSDK {
 
   /**
    * Get policy information
-   * @param {number} policyId - The policy ID to retrieve
+   * @param {Buffer} policyId - The policy ID returned by createPolicy
    * @param {string} sourceAccount - Account to pay for transaction
    * @returns {Promise<Object>} Policy data
    */
   async getPolicy(policyId, sourceAccount = TEST_USER_ADDRESS) {
     console.log('\n📄 Getting policy information...');
-    console.log(`   Policy ID: ${policyId}`);
+    console.log(`   Policy ID: ${policyId.toString('hex')}`);
 
     try {
       const account = await this.server.getAccount(sourceAccount);
@@ -123,7 +123,7 @@ class SmartContractSDK {
         .addOperation(Operation.invokeContractFunction({
           contract: this.contractId,
           function: 'get_policy',
-          args: [nativeToScVal(policyId, { type: 'u32' })]
+          args: [nativeToScVal(policyId, { type: 'bytes' })]
         }))
         .setTimeout(30)
         .build();
@@ -199,13 +199,13 @@ class SmartContractSDK {
 
   /**
    * Deactivate a policy
-   * @param {number} policyId - The policy ID to deactivate
+   * @param {Buffer} policyId - The policy ID to deactivate
    * @param {string} sourceAccount - Account to pay for transaction
    * @returns {Promise<void>}
    */
   async deactivatePolicy(policyId, sourceAccount = TEST_USER_ADDRESS) {
     console.log('\n🔴 Deactivating policy...');
-    console.log(`   Policy ID: ${policyId}`);
+    console.log(`   Policy ID: ${policyId.toString('hex')}`);
 
     try {
       const account = await this.server.getAccount(sourceAccount);
@@ -217,7 +217,7 @@ class SmartContractSDK {
         .addOperation(Operation.invokeContractFunction({
           contract: this.contractId,
           function: 'deactivate_policy',
-          args: [nativeToScVal(policyId, { type: 'u32' })]
+          args: [nativeToScVal(policyId, { type: 'bytes' })]
         }))
         .setTimeout(30)
         .build();
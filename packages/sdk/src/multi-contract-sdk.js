@@ -121,7 +121,7 @@ class DeFiInsuranceSDK {
 
   /**
    * Get insurance policy details
-   * @param {u32} policyId - Policy ID
+   * @param {Buffer} policyId - Policy ID
    */
   async getInsurancePolicy(policyId) {
     try {
@@ -133,7 +133,7 @@ class DeFiInsuranceSDK {
         account,
         this.contracts.simpleInsurance,
         'get_policy',
-        [nativeToScVal(policyId, { type: 'u32' })]
+        [nativeToScVal(policyId, { type: 'bytes' })]
       );
 
       if (!success) throw new Error(error);
@@ -173,7 +173,7 @@ class DeFiInsuranceSDK {
 
   /**
    * Deactivate insurance policy
-   * @param {u32} policyId - Policy ID
+   * @param {Buffer} policyId - Policy ID
    * @param {string} signerSecret - Signer private key
    */
   async deactivateInsurancePolicy(policyId, signerSecret) {
@@ -184,7 +184,7 @@ class DeFiInsuranceSDK {
         account,
         this.contracts.simpleInsurance,
         'deactivate_policy',
-        [nativeToScVal(policyId, { type: 'u32' })]
+        [nativeToScVal(policyId, { type: 'bytes' })]
       );
 
       if (!success) throw new Error(error);
@@ -238,7 +238,7 @@ class DeFiInsuranceSDK {
 
   /**
    * Withdraw from yield deposit
-   * @param {u64} depositId - Deposit ID
+   * @param {Buffer} depositId - Deposit ID
    * @param {i128} amount - Withdrawal amount
    * @param {string} signerSecret - Signer private key
    */
@@ -251,7 +251,7 @@ class DeFiInsuranceSDK {
         this.contracts.yieldAggregator,
         'withdraw',
         [
-          nativeToScVal(depositId, { type: 'u64' }),
+          nativeToScVal(depositId, { type: 'bytes' }),
           nativeToScVal(amount, { type: 'i128' })
         ]
       );
@@ -269,7 +269,7 @@ class DeFiInsuranceSDK {
 
   /**
    * Get yield deposit details
-   * @param {u64} depositId - Deposit ID
+   * @param {Buffer} depositId - Deposit ID
    */
   async getYieldDeposit(depositId) {
     try {
@@ -281,7 +281,7 @@ class DeFiInsuranceSDK {
         account,
         this.contracts.yieldAggregator,
         'get_deposit',
-        [nativeToScVal(depositId, { type: 'u64' })]
+        [nativeToScVal(depositId, { type: 'bytes' })]
       );
 
       if (!success) throw new Error(error);
@@ -318,7 +318,7 @@ class DeFiInsuranceSDK {
 
   /**
    * Add yield to deposit
-   * @param {u64} depositId - Deposit ID
+   * @param {Buffer} depositId - Deposit ID
    * @param {i128} yieldAmount - Yield amount to add
    * @param {string} signerSecret - Signer private key
    */
@@ -331,7 +331,7 @@ class DeFiInsuranceSDK {
         this.contracts.yieldAggregator,
         'add_yield',
         [
-          nativeToScVal(depositId, { type: 'u64' }),
+          nativeToScVal(depositId, { type: 'bytes' }),
           nativeToScVal(yieldAmount, { type: 'i128' })
         ]
       );
@@ -102,7 +102,7 @@ class TypeScriptSDKTester {
         .addOperation(Operation.invokeContractFunction({
           contract: contract,
           function: 'get_policy',
-          args: [nativeToScVal(policyId, { type: 'u32' })]
+          args: [nativeToScVal(policyId, { type: 'bytes' })]
         }))
         .setTimeout(30)
         .build();
@@ -89,7 +89,7 @@ class DeployedContractTester {
         .addOperation(Operation.invokeContractFunction({
           contract: CONTRACT_ID,
           function: 'get_policy',
-          args: [nativeToScVal(policyId, { type: 'u32' })]
+          args: [nativeToScVal(policyId, { type: 'bytes' })]
         }))
         .setTimeout(30)
         .build();
@@ -104,7 +104,7 @@ class MockContractTester {
             .addOperation(Operation.invokeContractFunction({
               contract: this.contractId,
               function: 'get_policy',
-              args: [nativeToScVal(policyId, { type: 'u32' })]
+              args: [nativeToScVal(policyId, { type: 'bytes' })]
             }))
             .setTimeout(30)
             .build();